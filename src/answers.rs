@@ -0,0 +1,119 @@
+use std::{fs, io};
+
+use serde::Deserialize;
+
+use crate::{
+    common::parsing,
+    error::Result,
+    input::InputsDir,
+    solution::{InputKind, Part},
+};
+
+/// Known answers for a day, stored in `answers.toml` next to the day's inputs:
+///
+/// ```toml
+/// [example]
+/// part_one = "24000"
+/// part_two = "45000"
+///
+/// [real]
+/// part_one = "..."
+/// ```
+///
+/// Any table or part may be left out if the answer isn't known yet.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Answers {
+    #[serde(default)]
+    pub example: PartAnswers,
+    #[serde(default)]
+    pub real: PartAnswers,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PartAnswers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl Answers {
+    pub const FILENAME: &'static str = "answers.toml";
+
+    /// Loads the answers for a day. A missing file means no answers are known.
+    pub fn load(inputs: &InputsDir, day_number: u32) -> Result<Self> {
+        match fs::read_to_string(inputs.day_dir(day_number).join(Self::FILENAME)) {
+            Ok(manifest) => Self::from_toml(&manifest),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn from_toml(manifest: &str) -> Result<Self> {
+        parsing::toml(manifest)
+    }
+
+    pub fn expected(&self, part: Part, kind: InputKind) -> Option<&str> {
+        let answers = match kind {
+            InputKind::Real => &self.real,
+            InputKind::Example => &self.example,
+        };
+        let answer = match part {
+            Part::One => &answers.part_one,
+            Part::Two => &answers.part_two,
+        };
+        answer.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{client::tests::temp_dir, error::Error};
+
+    #[test]
+    fn test_from_toml() {
+        let answers = Answers::from_toml(
+            r#"
+[example]
+part_one = "24000"
+part_two = "45000"
+
+[real]
+part_two = "1234"
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            answers.expected(Part::One, InputKind::Example),
+            Some("24000")
+        );
+        assert_eq!(
+            answers.expected(Part::Two, InputKind::Example),
+            Some("45000")
+        );
+        assert_eq!(answers.expected(Part::One, InputKind::Real), None);
+        assert_eq!(answers.expected(Part::Two, InputKind::Real), Some("1234"));
+    }
+
+    #[test]
+    fn test_from_toml_error_location() {
+        let err = Answers::from_toml("[example]\npart_three = \"1\"\n").unwrap_err();
+        assert!(matches!(err, Error::Parse { line: 2, .. }), "{err}");
+    }
+
+    #[test]
+    fn test_load_from_inputs_dir() {
+        let inputs = InputsDir(temp_dir("answers"));
+        assert_eq!(Answers::load(&inputs, 3).unwrap(), Answers::default());
+
+        fs::create_dir_all(inputs.day_dir(3)).unwrap();
+        fs::write(
+            inputs.day_dir(3).join(Answers::FILENAME),
+            "[real]\npart_one = \"157\"\n",
+        )
+        .unwrap();
+        let answers = Answers::load(&inputs, 3).unwrap();
+        assert_eq!(answers.expected(Part::One, InputKind::Real), Some("157"));
+    }
+}
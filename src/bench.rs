@@ -0,0 +1,175 @@
+use std::{fmt, time::Duration};
+
+use crate::{
+    error::Result,
+    input::InputsDir,
+    registry::Day,
+    solution::{InputKind, Part, Timings},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchOptions {
+    /// Runs that are done first and thrown away, to warm up caches and the allocator.
+    pub warmup: u32,
+    /// Runs that are measured. Must be at least 1.
+    pub repetitions: u32,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            warmup: 3,
+            repetitions: 25,
+        }
+    }
+}
+
+/// Summary statistics over a set of measurements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    /// Sample standard deviation, zero for a single measurement.
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Returns `None` if there are no samples.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let n = sorted.len();
+        let min = *sorted.first()?;
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+
+        let mean = sorted.iter().sum::<Duration>() / n as u32;
+        let variance = if n > 1 {
+            sorted
+                .iter()
+                .map(|sample| (sample.as_secs_f64() - mean.as_secs_f64()).powi(2))
+                .sum::<f64>()
+                / (n - 1) as f64
+        } else {
+            0.0
+        };
+
+        Some(Self {
+            min,
+            median,
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Report {
+    pub day: u32,
+    pub part: Part,
+    pub repetitions: u32,
+    pub parse: Stats,
+    pub solve: Stats,
+    pub total: Stats,
+}
+
+pub fn bench(
+    day: &Day,
+    part: Part,
+    inputs: &InputsDir,
+    kind: InputKind,
+    options: BenchOptions,
+) -> Result<Report> {
+    let input = inputs.read(day.number, kind)?;
+    for _ in 0..options.warmup {
+        day.solve_timed(&input, part, kind)?;
+    }
+
+    let samples = (0..options.repetitions.max(1))
+        .map(|_| {
+            day.solve_timed(&input, part, kind)
+                .map(|(_, timings)| timings)
+        })
+        .collect::<Result<Vec<Timings>>>()?;
+    let stats = |phase: fn(Timings) -> Duration| {
+        let durations = samples.iter().copied().map(phase).collect::<Vec<_>>();
+        Stats::from_samples(&durations).expect("at least one repetition is always run")
+    };
+
+    Ok(Report {
+        day: day.number,
+        part,
+        repetitions: samples.len() as u32,
+        parse: stats(|timings| timings.parse),
+        solve: stats(|timings| timings.solve),
+        total: stats(Timings::total),
+    })
+}
+
+/// Formats reports as a table with a parse, solve and total row for each day and part.
+pub struct Table<'a>(pub &'a [Report]);
+
+impl fmt::Display for Table<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>3}  {:>4}  {:<5}  {:>10}  {:>10}  {:>10}  {:>10}",
+            "Day", "Part", "Phase", "min", "median", "mean", "stddev"
+        )?;
+        for report in self.0 {
+            let phases = [
+                ("parse", report.parse),
+                ("solve", report.solve),
+                ("total", report.total),
+            ];
+            for (i, (phase, stats)) in phases.into_iter().enumerate() {
+                let (day, part) = if i == 0 {
+                    (report.day.to_string(), report.part.to_string())
+                } else {
+                    Default::default()
+                };
+                writeln!(
+                    f,
+                    "{day:>3}  {part:>4}  {phase:<5}  {:>10}  {:>10}  {:>10}  {:>10}",
+                    format!("{:.2?}", stats.min),
+                    format!("{:.2?}", stats.median),
+                    format!("{:.2?}", stats.mean),
+                    format!("{:.2?}", stats.stddev),
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples = [4, 1, 3, 2].map(Duration::from_millis);
+        let stats = Stats::from_samples(&samples).unwrap();
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean, Duration::from_micros(2500));
+        // sqrt(5 / 3) ms
+        assert_eq!(stats.stddev.as_micros(), 1290);
+    }
+
+    #[test]
+    fn test_stats_single_sample() {
+        let stats = Stats::from_samples(&[Duration::from_millis(7)]).unwrap();
+        assert_eq!(stats.median, Duration::from_millis(7));
+        assert_eq!(stats.stddev, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_stats_no_samples() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+}
@@ -0,0 +1,253 @@
+use std::{env, fs, io, time::Duration};
+
+use crate::{
+    error::{Error, Result},
+    input::InputsDir,
+    solution::{InputKind, Part},
+};
+
+/// Talks to the Advent of Code website, or anything that serves the same paths.
+#[derive(Debug, Clone)]
+pub struct Client {
+    base_url: String,
+    session: Option<String>,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub const DEFAULT_BASE_URL: &'static str = "https://adventofcode.com/2022";
+    /// Environment variable that overrides [`Client::DEFAULT_BASE_URL`].
+    pub const BASE_URL_VAR: &'static str = "AOC_BASE_URL";
+    /// Environment variable holding the `session` cookie of a logged in browser.
+    pub const SESSION_VAR: &'static str = "AOC_SESSION";
+
+    /// `base_url` is the year's page, like [`Client::DEFAULT_BASE_URL`].
+    pub fn new(base_url: impl Into<String>, session: Option<String>) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .user_agent(concat!(
+                env!("CARGO_PKG_NAME"),
+                "/",
+                env!("CARGO_PKG_VERSION")
+            ))
+            .build();
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session,
+            agent,
+        }
+    }
+
+    /// Reads the base URL and session cookie from `AOC_BASE_URL` and `AOC_SESSION`.
+    pub fn from_env() -> Self {
+        let var = |name| env::var(name).ok().filter(|value| !value.trim().is_empty());
+        Self::new(
+            var(Self::BASE_URL_VAR).unwrap_or_else(|| Self::DEFAULT_BASE_URL.to_string()),
+            var(Self::SESSION_VAR).map(|session| session.trim().to_string()),
+        )
+    }
+
+    /// Downloads a day's puzzle input. Prefer [`cached_input`], which only does this once.
+    pub fn fetch_input(&self, day_number: u32) -> Result<String> {
+        let url = format!("{}/day/{day_number}/input", self.base_url);
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &self.cookie()?)
+            .call()
+            .map_err(|err| request_error(&url, err))?;
+        Ok(response.into_string()?)
+    }
+
+    /// Posts an answer and returns the page the server responded with.
+    pub fn post_answer(&self, day_number: u32, part: Part, answer: &str) -> Result<String> {
+        let url = format!("{}/day/{day_number}/answer", self.base_url);
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie()?)
+            .send_form(&[("level", &part.number().to_string()), ("answer", answer)])
+            .map_err(|err| request_error(&url, err))?;
+        Ok(response.into_string()?)
+    }
+
+    fn cookie(&self) -> Result<String> {
+        self.session
+            .as_ref()
+            .map(|session| format!("session={session}"))
+            .ok_or_else(|| {
+                Error::http(format!(
+                    "no session cookie, set {} to the `session` cookie of a logged in browser",
+                    Self::SESSION_VAR
+                ))
+            })
+    }
+}
+
+/// Returns a day's real input from `inputs`, downloading and storing it first if it isn't there.
+pub fn cached_input(client: &Client, inputs: &InputsDir, day_number: u32) -> Result<String> {
+    let path = inputs.input_path(day_number, InputKind::Real);
+    match fs::read_to_string(&path) {
+        Ok(input) => return Ok(input),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err.into()),
+    }
+
+    let input = client.fetch_input(day_number)?;
+    fs::create_dir_all(inputs.day_dir(day_number))?;
+    // Write to a temporary file first, so an interrupted download is never mistaken for an input.
+    let partial = path.with_extension("txt.partial");
+    fs::write(&partial, &input)?;
+    fs::rename(&partial, &path)?;
+    Ok(input)
+}
+
+fn request_error(url: &str, err: ureq::Error) -> Error {
+    match err {
+        ureq::Error::Status(400, _) => {
+            Error::http(format!("{url}: the session cookie was rejected"))
+        }
+        ureq::Error::Status(404, _) => Error::http(format!(
+            "{url}: not found, the puzzle may not be unlocked yet"
+        )),
+        ureq::Error::Status(status, response) => Error::http(format!(
+            "{url}: server responded with {status} {}",
+            response.status_text()
+        )),
+        ureq::Error::Transport(err) => Error::http(format!("{url}: {err}")),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        path::PathBuf,
+        sync::{Arc, Mutex},
+        thread,
+    };
+
+    use super::*;
+
+    /// A request received by [`MockServer`].
+    #[derive(Debug, Clone)]
+    pub struct Request {
+        pub method: String,
+        pub path: String,
+        pub cookie: Option<String>,
+        pub body: String,
+    }
+
+    /// Serves canned responses on localhost and records the requests it gets.
+    pub struct MockServer {
+        pub url: String,
+        pub requests: Arc<Mutex<Vec<Request>>>,
+    }
+
+    impl MockServer {
+        /// `respond` maps a request to a status code and body.
+        pub fn start(respond: impl Fn(&Request) -> (u16, String) + Send + 'static) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}/2022", listener.local_addr().unwrap());
+            let requests = Arc::new(Mutex::new(Vec::new()));
+            let recorded = Arc::clone(&requests);
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let mut stream = stream.unwrap();
+                    let request = read_request(&mut BufReader::new(&stream));
+                    let (status, body) = respond(&request);
+                    recorded.lock().unwrap().push(request);
+                    write!(
+                        stream,
+                        "HTTP/1.1 {status} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    )
+                    .unwrap();
+                }
+            });
+            Self { url, requests }
+        }
+
+        pub fn requests(&self) -> Vec<Request> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    fn read_request(reader: &mut impl BufRead) -> Request {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let mut parts = line.split_whitespace();
+        let method = parts.next().unwrap_or_default().to_string();
+        let path = parts.next().unwrap_or_default().to_string();
+
+        let mut cookie = None;
+        let mut content_length = 0;
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).unwrap();
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            let (name, value) = header.split_once(':').unwrap();
+            match name.to_ascii_lowercase().as_str() {
+                "cookie" => cookie = Some(value.trim().to_string()),
+                "content-length" => content_length = value.trim().parse().unwrap(),
+                _ => {}
+            }
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+        Request {
+            method,
+            path,
+            cookie,
+            body: String::from_utf8(body).unwrap(),
+        }
+    }
+
+    /// An empty directory that's unique to a test.
+    pub fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-2022-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_cached_input_fetches_once() {
+        let server = MockServer::start(|request| (200, format!("input for {}", request.path)));
+        let client = Client::new(&server.url, Some("abc".to_string()));
+        let inputs = InputsDir(temp_dir("fetch-once"));
+
+        for _ in 0..2 {
+            assert_eq!(
+                cached_input(&client, &inputs, 3).unwrap(),
+                "input for /2022/day/3/input"
+            );
+        }
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].cookie.as_deref(), Some("session=abc"));
+        assert_eq!(
+            inputs.read(3, InputKind::Real).unwrap(),
+            "input for /2022/day/3/input"
+        );
+    }
+
+    #[test]
+    fn test_fetch_errors() {
+        let server = MockServer::start(|_| (404, "Not Found".to_string()));
+        let client = Client::new(&server.url, Some("abc".to_string()));
+        let inputs = InputsDir(temp_dir("fetch-errors"));
+
+        let err = cached_input(&client, &inputs, 25).unwrap_err();
+        assert!(matches!(err, Error::Http(_)), "{err}");
+        assert!(!inputs.input_path(25, InputKind::Real).exists());
+
+        let err = Client::new(&server.url, None).fetch_input(1).unwrap_err();
+        assert!(err.to_string().contains(Client::SESSION_VAR), "{err}");
+    }
+}
@@ -0,0 +1,188 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// Where the sequence `x0, step(x0), step(step(x0)), ...` starts repeating itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    /// The index of the first state that is part of the cycle.
+    pub start: usize,
+    /// The number of states in the cycle, at least 1.
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The smallest index whose state is the same as the state at `index`.
+    pub fn reduce(self, index: usize) -> usize {
+        if index < self.start {
+            index
+        } else {
+            self.start + (index - self.start) % self.length
+        }
+    }
+}
+
+/// Floyd's tortoise and hare. Keeps only two states at a time, but steps about three times as
+/// often as the sequence is long.
+///
+/// Never returns if the sequence doesn't repeat.
+pub fn floyd<T: Clone + Eq>(initial: T, mut step: impl FnMut(&T) -> T) -> Cycle {
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let halfway = step(&hare);
+        hare = step(&halfway);
+    }
+
+    // The hare is now a multiple of the cycle length ahead, so they meet again at its start.
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+    Cycle { start, length }
+}
+
+/// Brent's algorithm. Keeps only two states at a time, like [`floyd`], but needs fewer steps.
+///
+/// Never returns if the sequence doesn't repeat.
+pub fn brent<T: Clone + Eq>(initial: T, mut step: impl FnMut(&T) -> T) -> Cycle {
+    // Look for the length first, with the tortoise teleporting to the hare at powers of two.
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // Then start them `length` apart, so they meet at the start of the cycle.
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    Cycle { start, length }
+}
+
+/// Remembers every state until one repeats, so each state is only computed once.
+///
+/// Returns the cycle and the states before its end, so `states[cycle.reduce(n)]` is the `n`th
+/// state for any `n`. Never returns if the sequence doesn't repeat.
+pub fn find_cycle<T: Clone + Eq + Hash>(
+    initial: T,
+    mut step: impl FnMut(&T) -> T,
+) -> (Cycle, Vec<T>) {
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = initial;
+    loop {
+        if let Some(&start) = seen.get(&state) {
+            let length = states.len() - start;
+            return (Cycle { start, length }, states);
+        }
+        let next = step(&state);
+        seen.insert(state.clone(), states.len());
+        states.push(state);
+        state = next;
+    }
+}
+
+/// The state after `n` steps, skipping ahead as soon as a state repeats, so `n` can be huge as
+/// long as the sequence repeats early enough.
+pub fn nth_state<T: Clone + Eq + Hash>(initial: T, mut step: impl FnMut(&T) -> T, n: usize) -> T {
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = initial;
+    while states.len() < n {
+        if let Some(&start) = seen.get(&state) {
+            let cycle = Cycle {
+                start,
+                length: states.len() - start,
+            };
+            return states.swap_remove(cycle.reduce(n));
+        }
+        let next = step(&state);
+        seen.insert(state.clone(), states.len());
+        states.push(state);
+        state = next;
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 3, 12, 2, 7, 23, 10, 16, 27, 7, ... which repeats from index 3 every 5 steps.
+    fn step(&x: &u32) -> u32 {
+        (x * x + 3) % 29
+    }
+
+    const CYCLE: Cycle = Cycle {
+        start: 3,
+        length: 5,
+    };
+
+    #[test]
+    fn test_floyd() {
+        assert_eq!(floyd(3, step), CYCLE);
+        assert_eq!(
+            floyd(0, |_: &u32| 0),
+            Cycle {
+                start: 0,
+                length: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_brent() {
+        assert_eq!(brent(3, step), CYCLE);
+        assert_eq!(
+            brent(0, |_: &u32| 0),
+            Cycle {
+                start: 0,
+                length: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let (cycle, states) = find_cycle(3, step);
+        assert_eq!(cycle, CYCLE);
+        assert_eq!(states, [3, 12, 2, 7, 23, 10, 16, 27]);
+        assert_eq!(cycle.reduce(2), 2);
+        assert_eq!(cycle.reduce(1_000_000_000), 5);
+    }
+
+    #[test]
+    fn test_nth_state() {
+        let brute_force = |n| (0..n).fold(3, |x, _| step(&x));
+        for n in [0, 1, 3, 7, 8, 100] {
+            assert_eq!(nth_state(3, step, n), brute_force(n), "n = {n}");
+        }
+        assert_eq!(nth_state(3, step, 1_000_000_000), 10);
+    }
+}
@@ -0,0 +1,367 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use glam::IVec2;
+
+use crate::{
+    common::math::{Direction4, Direction8},
+    error::{Error, Result},
+};
+
+/// A rectangular 2-D board stored row by row. `(0, 0)` is the top left cell and y grows downwards.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Returns `None` if there aren't exactly `width * height` cells.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Option<Self> {
+        (cells.len() == width * height).then_some(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut cell: impl FnMut(IVec2) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| IVec2::new(x as i32, y as i32)))
+            .map(&mut cell)
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses a map with one character per cell and one line per row, where `cell` returns `None`
+    /// for characters that aren't a valid `what`.
+    pub fn parse(input: &str, what: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Self> {
+        let lines = input.trim_end_matches(['\n', '\r']).lines();
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for line in lines {
+            let row_start = cells.len();
+            for (i, ch) in line.char_indices() {
+                let value = cell(ch).ok_or_else(|| {
+                    Error::parse(input, &line[i..], format!("invalid {what}: {ch:?}"))
+                })?;
+                cells.push(value);
+            }
+            let row_width = cells.len() - row_start;
+            if *width.get_or_insert(row_width) != row_width {
+                return Err(Error::parse(
+                    input,
+                    line,
+                    format!("expected {} cells in every row", width.unwrap_or_default()),
+                ));
+            }
+            height += 1;
+        }
+
+        match width {
+            Some(width) if width > 0 => Ok(Self {
+                width,
+                height,
+                cells,
+            }),
+            _ => Err(Error::parse(input, input, "the grid is empty")),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn size(&self) -> IVec2 {
+        IVec2::new(self.width as i32, self.height as i32)
+    }
+
+    pub fn contains(&self, pos: IVec2) -> bool {
+        pos.x >= 0 && pos.y >= 0 && (pos.x as usize) < self.width && (pos.y as usize) < self.height
+    }
+
+    pub fn get(&self, pos: IVec2) -> Option<&T> {
+        self.index_of(pos).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, pos: IVec2) -> Option<&mut T> {
+        self.index_of(pos).map(|index| &mut self.cells[index])
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = IVec2> {
+        let width = self.width;
+        (0..self.cells.len())
+            .map(move |index| IVec2::new((index % width) as i32, (index / width) as i32))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (IVec2, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The orthogonal neighbours of `pos` that are inside the grid.
+    pub fn neighbours4(&self, pos: IVec2) -> impl Iterator<Item = IVec2> + '_ {
+        self.neighbours(pos, Direction4::ALL.map(Direction4::to_ivec2))
+    }
+
+    /// The orthogonal and diagonal neighbours of `pos` that are inside the grid.
+    pub fn neighbours8(&self, pos: IVec2) -> impl Iterator<Item = IVec2> + '_ {
+        self.neighbours(pos, Direction8::ALL.map(Direction8::to_ivec2))
+    }
+
+    fn neighbours<const N: usize>(
+        &self,
+        pos: IVec2,
+        offsets: [IVec2; N],
+    ) -> impl Iterator<Item = IVec2> + '_ {
+        offsets
+            .into_iter()
+            .map(move |offset| pos + offset)
+            .filter(|&neighbour| self.contains(neighbour))
+    }
+
+    /// # Panics
+    ///
+    /// If `y` is out of bounds.
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// # Panics
+    ///
+    /// If `x` is out of bounds.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is out of bounds");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// The cells from `from` (exclusive) in steps of `step` up to the edge of the grid.
+    pub fn ray(&self, from: IVec2, step: IVec2) -> impl Iterator<Item = (IVec2, &T)> {
+        assert_ne!(step, IVec2::ZERO, "a ray needs a direction");
+        std::iter::successors(Some(from + step), move |&pos| Some(pos + step))
+            .map_while(|pos| self.get(pos).map(|cell| (pos, cell)))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    fn index_of(&self, pos: IVec2) -> Option<usize> {
+        self.contains(pos)
+            .then(|| pos.y as usize * self.width + pos.x as usize)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Mirrors the grid along its top-left to bottom-right diagonal.
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.height, self.width, |pos| {
+            self[IVec2::new(pos.y, pos.x)].clone()
+        })
+    }
+
+    pub fn rotate_clockwise(&self) -> Self {
+        let height = self.height as i32;
+        Self::from_fn(self.height, self.width, |pos| {
+            self[IVec2::new(pos.y, height - 1 - pos.x)].clone()
+        })
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Self {
+        let width = self.width as i32;
+        Self::from_fn(self.height, self.width, |pos| {
+            self[IVec2::new(width - 1 - pos.y, pos.x)].clone()
+        })
+    }
+}
+
+/// What can be seen from a cell when looking in one direction over a height map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Sight {
+    /// Whether every cell up to the edge is lower, so the cell can be seen from outside the grid.
+    pub to_edge: bool,
+    /// How many cells can be seen, up to and including the first one at least as high.
+    pub distance: usize,
+}
+
+impl<T: Ord> Grid<T> {
+    /// What every cell sees looking in `direction`, treating the cells as heights.
+    ///
+    /// Sweeps each line once from the edge `direction` points at, with a stack of the cells that
+    /// haven't been hidden by a higher one yet, so it takes linear time.
+    pub fn sights(&self, direction: Direction4) -> Grid<Sight> {
+        let step = direction.to_ivec2();
+        let mut sights = Grid::filled(self.width, self.height, Sight::default());
+        let edge = self
+            .positions()
+            .filter(|&pos| !self.contains(pos + step))
+            .collect::<Vec<_>>();
+        for start in edge {
+            let line = std::iter::successors(Some(start), |&pos| Some(pos - step))
+                .take_while(|&pos| self.contains(pos));
+            // Indices along the line, with their heights decreasing from the bottom of the stack.
+            let mut unhidden: Vec<(usize, IVec2)> = Vec::new();
+            for (index, pos) in line.enumerate() {
+                while unhidden
+                    .last()
+                    .is_some_and(|&(_, other)| self[other] < self[pos])
+                {
+                    unhidden.pop();
+                }
+                sights[pos] = match unhidden.last() {
+                    Some(&(other_index, _)) => Sight {
+                        to_edge: false,
+                        distance: index - other_index,
+                    },
+                    None => Sight {
+                        to_edge: true,
+                        distance: index,
+                    },
+                };
+                unhidden.push((index, pos));
+            }
+        }
+        sights
+    }
+}
+
+impl<T> Index<IVec2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: IVec2) -> &Self::Output {
+        match self.get(pos) {
+            Some(cell) => cell,
+            None => panic!(
+                "{pos} is outside of the {}x{} grid",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<IVec2> for Grid<T> {
+    fn index_mut(&mut self, pos: IVec2) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(pos) {
+            Some(cell) => cell,
+            None => panic!("{pos} is outside of the {width}x{height} grid"),
+        }
+    }
+}
+
+/// Writes one line per row, without a trailing newline.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::parse("abc\ndef\n", "letter", Some).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[IVec2::new(2, 1)], 'f');
+        assert_eq!(grid.get(IVec2::new(3, 0)), None);
+        assert_eq!(grid.to_string(), "abc\ndef");
+
+        let err = Grid::parse("ab\nabc", "letter", Some).unwrap_err();
+        assert!(matches!(err, Error::Parse { line: 2, .. }), "{err}");
+        let err =
+            Grid::parse("ab\na?", "letter", |ch| ch.is_alphabetic().then_some(ch)).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                Error::Parse {
+                    line: 2,
+                    column: 2,
+                    ..
+                }
+            ),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = grid();
+        let mut neighbours = grid.neighbours4(IVec2::ZERO).collect::<Vec<_>>();
+        neighbours.sort_by_key(|pos| (pos.y, pos.x));
+        assert_eq!(neighbours, [IVec2::X, IVec2::Y]);
+        assert_eq!(grid.neighbours8(IVec2::new(1, 0)).count(), 5);
+    }
+
+    #[test]
+    fn test_lines() {
+        let grid = grid();
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        let ray = grid.ray(IVec2::ZERO, IVec2::X).map(|(_, &ch)| ch);
+        assert_eq!(ray.collect::<String>(), "bc");
+    }
+
+    #[test]
+    fn test_sights() {
+        let heights = Grid::parse("3153\n2562", "height", |ch| ch.to_digit(10)).unwrap();
+        let sights = heights.sights(Direction4::Right);
+        let to_edge = sights.map(|sight| if sight.to_edge { '#' } else { '.' });
+        assert_eq!(to_edge.to_string(), "..##\n..##");
+        let distances = sights.map(|sight| sight.distance);
+        assert_eq!(distances.to_string(), "2110\n1110");
+        let distances = heights.sights(Direction4::Up).map(|sight| sight.distance);
+        assert_eq!(distances.to_string(), "0000\n1111");
+    }
+
+    #[test]
+    fn test_transform() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    }
+}
@@ -0,0 +1,87 @@
+//! Pictures as grids of colours, for drawing what a solution did.
+
+use std::io::{self, Write};
+
+use glam::IVec2;
+
+use crate::common::grid::Grid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Self = Self(0, 0, 0);
+    pub const WHITE: Self = Self(255, 255, 255);
+    pub const RED: Self = Self(220, 30, 30);
+
+    /// The colour `t` of the way from `self` to `other`, with `t` clamped to `0.0..=1.0`.
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let channel =
+            |a: u8, b: u8| (f32::from(a) + (f32::from(b) - f32::from(a)) * t).round() as u8;
+        Self(
+            channel(self.0, other.0),
+            channel(self.1, other.1),
+            channel(self.2, other.2),
+        )
+    }
+
+    /// The colour `t` of the way along evenly spaced `stops`.
+    ///
+    /// # Panics
+    ///
+    /// If there are no stops.
+    pub fn gradient(stops: &[Self], t: f32) -> Self {
+        assert!(!stops.is_empty(), "a gradient needs at least one colour");
+        let position = t.clamp(0.0, 1.0) * (stops.len() - 1) as f32;
+        let index = (position as usize).min(stops.len().saturating_sub(2));
+        match stops.get(index + 1) {
+            Some(&next) => stops[index].lerp(next, position - index as f32),
+            None => stops[index],
+        }
+    }
+}
+
+/// Blows every cell up into a `factor` by `factor` square of pixels.
+pub fn scale(image: &Grid<Rgb>, factor: usize) -> Grid<Rgb> {
+    let factor = factor.max(1);
+    Grid::from_fn(image.width() * factor, image.height() * factor, |pos| {
+        image[pos / IVec2::splat(factor as i32)]
+    })
+}
+
+/// Writes the image as a binary PPM, which most image viewers can open.
+pub fn write_ppm(image: &Grid<Rgb>, mut out: impl Write) -> io::Result<()> {
+    write!(out, "P6\n{} {}\n255\n", image.width(), image.height())?;
+    let pixels = image
+        .iter()
+        .flat_map(|(_, &Rgb(r, g, b))| [r, g, b])
+        .collect::<Vec<_>>();
+    out.write_all(&pixels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gradient() {
+        assert_eq!(Rgb::BLACK.lerp(Rgb::WHITE, 0.5), Rgb(128, 128, 128));
+        let stops = [Rgb::BLACK, Rgb(0, 200, 0), Rgb::WHITE];
+        assert_eq!(Rgb::gradient(&stops, 0.0), Rgb::BLACK);
+        assert_eq!(Rgb::gradient(&stops, 0.5), Rgb(0, 200, 0));
+        assert_eq!(Rgb::gradient(&stops, 1.0), Rgb::WHITE);
+        assert_eq!(Rgb::gradient(&[Rgb::RED], 0.3), Rgb::RED);
+    }
+
+    #[test]
+    fn test_write_ppm() {
+        let image = scale(&Grid::filled(1, 1, Rgb(1, 2, 3)), 2);
+        let mut ppm = Vec::new();
+        write_ppm(&image, &mut ppm).unwrap();
+        assert_eq!(
+            ppm,
+            b"P6\n2 2\n255\n\x01\x02\x03\x01\x02\x03\x01\x02\x03\x01\x02\x03"
+        );
+    }
+}
@@ -0,0 +1,288 @@
+use std::fmt;
+
+/// A non-empty range of integers, with both ends included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval {
+    start: i64,
+    end: i64,
+}
+
+impl Interval {
+    /// Returns `None` if `start` is greater than `end`.
+    pub fn new(start: i64, end: i64) -> Option<Self> {
+        (start <= end).then_some(Self { start, end })
+    }
+
+    pub fn point(value: i64) -> Self {
+        Self {
+            start: value,
+            end: value,
+        }
+    }
+
+    pub fn start(self) -> i64 {
+        self.start
+    }
+
+    pub fn end(self) -> i64 {
+        self.end
+    }
+
+    /// The number of integers in the interval.
+    #[allow(clippy::len_without_is_empty)] // intervals are never empty
+    pub fn len(self) -> u64 {
+        self.end.abs_diff(self.start) + 1
+    }
+
+    pub fn contains(self, value: i64) -> bool {
+        (self.start..=self.end).contains(&value)
+    }
+
+    pub fn contains_interval(self, other: Self) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub fn overlaps(self, other: Self) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    pub fn intersection(self, other: Self) -> Option<Self> {
+        Self::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// Whether the two intervals overlap or are next to each other, so their union is one interval.
+    fn touches(self, other: Self) -> bool {
+        self.start <= other.end.saturating_add(1) && other.start <= self.end.saturating_add(1)
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..={}", self.start, self.end)
+    }
+}
+
+/// A set of integers stored as sorted intervals that neither overlap nor touch.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The number of integers in the set.
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().map(|interval| interval.len()).sum()
+    }
+
+    /// The intervals making up the set, in ascending order.
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    /// The smallest interval containing the whole set.
+    pub fn span(&self) -> Option<Interval> {
+        let first = self.intervals.first()?;
+        let last = self.intervals.last()?;
+        Some(Interval {
+            start: first.start,
+            end: last.end,
+        })
+    }
+
+    pub fn insert(&mut self, interval: Interval) {
+        let first = self
+            .intervals
+            .partition_point(|other| other.end.saturating_add(1) < interval.start);
+        let last = first
+            + self.intervals[first..]
+                .iter()
+                .take_while(|other| other.touches(interval))
+                .count();
+        let merged = self.intervals[first..last]
+            .iter()
+            .fold(interval, |merged, other| Interval {
+                start: merged.start.min(other.start),
+                end: merged.end.max(other.end),
+            });
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.contains_interval(Interval::point(value))
+    }
+
+    pub fn contains_interval(&self, interval: Interval) -> bool {
+        let index = self
+            .intervals
+            .partition_point(|other| other.end < interval.start);
+        self.intervals
+            .get(index)
+            .is_some_and(|other| other.contains_interval(interval))
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.intervals
+            .iter()
+            .chain(&other.intervals)
+            .copied()
+            .collect()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while let (Some(&a), Some(&b)) = (self.intervals.get(i), other.intervals.get(j)) {
+            intervals.extend(a.intersection(b));
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { intervals }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let mut j = 0;
+        for &interval in &self.intervals {
+            while other
+                .intervals
+                .get(j)
+                .is_some_and(|b| b.end < interval.start)
+            {
+                j += 1;
+            }
+            let mut rest = Some(interval);
+            for &b in other.intervals[j..]
+                .iter()
+                .take_while(|b| b.start <= interval.end)
+            {
+                let Some(remaining) = rest else { break };
+                intervals.extend(Interval::new(remaining.start, b.start.saturating_sub(1)));
+                rest = b
+                    .end
+                    .checked_add(1)
+                    .and_then(|start| Interval::new(start, remaining.end));
+            }
+            intervals.extend(rest);
+        }
+        Self { intervals }
+    }
+
+    /// The intervals between the ones in the set, in ascending order.
+    pub fn gaps(&self) -> impl Iterator<Item = Interval> + '_ {
+        self.intervals.windows(2).map(|pair| Interval {
+            start: pair[0].end + 1,
+            end: pair[1].start - 1,
+        })
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        let mut sorted = iter.into_iter().collect::<Vec<_>>();
+        sorted.sort_unstable();
+        let mut intervals: Vec<Interval> = Vec::with_capacity(sorted.len());
+        for interval in sorted {
+            match intervals.last_mut() {
+                Some(last) if last.touches(interval) => last.end = last.end.max(interval.end),
+                _ => intervals.push(interval),
+            }
+        }
+        Self { intervals }
+    }
+}
+
+impl From<Interval> for IntervalSet {
+    fn from(interval: Interval) -> Self {
+        Self {
+            intervals: vec![interval],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interval(start: i64, end: i64) -> Interval {
+        Interval::new(start, end).unwrap()
+    }
+
+    fn set(intervals: &[(i64, i64)]) -> IntervalSet {
+        intervals
+            .iter()
+            .map(|&(start, end)| interval(start, end))
+            .collect()
+    }
+
+    #[test]
+    fn test_interval() {
+        assert_eq!(Interval::new(3, 2), None);
+        assert_eq!(interval(-2, 2).len(), 5);
+        assert!(interval(2, 8).contains_interval(interval(3, 7)));
+        assert!(interval(2, 4).overlaps(interval(4, 6)));
+        assert!(!interval(2, 3).overlaps(interval(4, 6)));
+        assert_eq!(
+            interval(2, 6).intersection(interval(4, 8)),
+            Some(interval(4, 6))
+        );
+    }
+
+    #[test]
+    fn test_normalised() {
+        let set = set(&[(5, 7), (1, 2), (3, 3), (10, 12), (6, 8)]);
+        assert_eq!(
+            set.intervals(),
+            [interval(1, 3), interval(5, 8), interval(10, 12)]
+        );
+        assert_eq!(set.len(), 10);
+        assert_eq!(
+            set.gaps().collect::<Vec<_>>(),
+            [interval(4, 4), interval(9, 9)]
+        );
+        assert_eq!(set.span(), Some(interval(1, 12)));
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut set = set(&[(1, 2), (5, 6), (9, 10)]);
+        set.insert(interval(3, 5));
+        assert_eq!(set.intervals(), [interval(1, 6), interval(9, 10)]);
+        set.insert(interval(12, 12));
+        assert_eq!(
+            set.intervals(),
+            [interval(1, 6), interval(9, 10), interval(12, 12)]
+        );
+        set.insert(interval(0, 20));
+        assert_eq!(set.intervals(), [interval(0, 20)]);
+    }
+
+    #[test]
+    fn test_contains() {
+        let set = set(&[(1, 3), (5, 8)]);
+        assert!(set.contains(6));
+        assert!(!set.contains(4));
+        assert!(set.contains_interval(interval(5, 8)));
+        assert!(!set.contains_interval(interval(3, 5)));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[(0, 10), (20, 30)]);
+        let b = set(&[(5, 25), (28, 28)]);
+        assert_eq!(a.union(&b), set(&[(0, 30)]));
+        assert_eq!(a.intersection(&b), set(&[(5, 10), (20, 25), (28, 28)]));
+        assert_eq!(a.difference(&b), set(&[(0, 4), (26, 27), (29, 30)]));
+        assert_eq!(b.difference(&a), set(&[(11, 19)]));
+    }
+}
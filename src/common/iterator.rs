@@ -1,4 +1,5 @@
-use std::collections::HashSet;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::hash::Hash;
 
 pub fn is_all_unique<T: Hash + Eq>(it: impl IntoIterator<Item = T>) -> bool {
@@ -12,6 +13,112 @@ pub fn is_all_unique<T: Hash + Eq>(it: impl IntoIterator<Item = T>) -> bool {
     true
 }
 
+/// The start of the first `k` consecutive items that are all different, in a single pass.
+pub fn first_distinct_window<T: Hash + Eq>(
+    items: impl IntoIterator<Item = T>,
+    k: usize,
+) -> Option<usize> {
+    let mut last_seen = HashMap::new();
+    let mut start = 0;
+    for (index, item) in items.into_iter().enumerate() {
+        if let Some(previous) = last_seen.insert(item, index) {
+            start = start.max(previous + 1);
+        }
+        if index + 1 - start == k {
+            return Some(start);
+        }
+    }
+    (k == 0).then_some(0)
+}
+
+/// Like [`first_distinct_window`], but with a table instead of a hash map.
+pub fn first_distinct_byte_window(bytes: &[u8], k: usize) -> Option<usize> {
+    let mut last_seen = [None; 256];
+    let mut start = 0;
+    for (index, &byte) in bytes.iter().enumerate() {
+        if let Some(previous) = last_seen[usize::from(byte)].replace(index) {
+            start = start.max(previous + 1);
+        }
+        if index + 1 - start == k {
+            return Some(start);
+        }
+    }
+    (k == 0).then_some(0)
+}
+
+/// Selection helpers that look at every item once and keep at most `k` of them in a heap.
+pub trait IteratorExt: Iterator + Sized {
+    /// The `k` largest items, largest first.
+    fn top_k(self, k: usize) -> Vec<Self::Item>
+    where
+        Self::Item: Ord,
+    {
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        for item in self {
+            push_bounded(&mut heap, Reverse(item), k);
+        }
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(item)| item)
+            .collect()
+    }
+
+    /// The `k` smallest items, smallest first.
+    fn bottom_k(self, k: usize) -> Vec<Self::Item>
+    where
+        Self::Item: Ord,
+    {
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        for item in self {
+            push_bounded(&mut heap, item, k);
+        }
+        heap.into_sorted_vec()
+    }
+
+    /// The `k`th smallest item, counting from zero, or `None` if there are `k` items or fewer.
+    fn k_th(self, k: usize) -> Option<Self::Item>
+    where
+        Self::Item: Ord,
+    {
+        let mut heap = BinaryHeap::with_capacity(k + 2);
+        for item in self {
+            push_bounded(&mut heap, item, k + 1);
+        }
+        (heap.len() == k + 1).then(|| heap.pop()).flatten()
+    }
+
+    /// The smallest and the largest item. For ties, the first of the smallest and the last of the
+    /// largest, like [`Iterator::min`] and [`Iterator::max`].
+    fn min_max(mut self) -> Option<(Self::Item, Self::Item)>
+    where
+        Self::Item: Ord + Clone,
+    {
+        let first = self.next()?;
+        Some(self.fold((first.clone(), first), |(min, max), item| {
+            if item < min {
+                (item, max)
+            } else if item >= max {
+                (min, item)
+            } else {
+                (min, max)
+            }
+        }))
+    }
+}
+
+impl<I: Iterator> IteratorExt for I {}
+
+/// Pushes `item` and drops the largest item if that leaves more than `k`.
+fn push_bounded<T: Ord>(heap: &mut BinaryHeap<T>, item: T, k: usize) {
+    if heap.len() < k {
+        heap.push(item);
+    } else if let Some(mut largest) = heap.peek_mut() {
+        if item < *largest {
+            *largest = item;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -27,4 +134,46 @@ mod tests {
         let it = [1, 2, 3, 1];
         assert!(!is_all_unique(it));
     }
+
+    #[test]
+    fn test_first_distinct_window() {
+        let datastream = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+        assert_eq!(first_distinct_window(datastream.chars(), 4), Some(3));
+        assert_eq!(first_distinct_window(datastream.chars(), 14), Some(5));
+        assert_eq!(
+            first_distinct_byte_window(datastream.as_bytes(), 4),
+            Some(3)
+        );
+        assert_eq!(
+            first_distinct_byte_window(datastream.as_bytes(), 14),
+            Some(5)
+        );
+        assert_eq!(first_distinct_byte_window(b"aaaa", 2), None);
+        assert_eq!(first_distinct_byte_window(b"abc", 4), None);
+        assert_eq!(first_distinct_window([1, 2, 1], 0), Some(0));
+    }
+
+    #[test]
+    fn test_top_k() {
+        let items = [5, 1, 9, 3, 7, 9];
+        assert_eq!(items.into_iter().top_k(3), [9, 9, 7]);
+        assert_eq!(items.into_iter().bottom_k(2), [1, 3]);
+        assert_eq!(items.into_iter().top_k(10).len(), 6);
+        assert_eq!(items.into_iter().top_k(0), []);
+    }
+
+    #[test]
+    fn test_k_th() {
+        let items = [5, 1, 9, 3, 7];
+        assert_eq!(items.into_iter().k_th(0), Some(1));
+        assert_eq!(items.into_iter().k_th(2), Some(5));
+        assert_eq!(items.into_iter().k_th(5), None);
+    }
+
+    #[test]
+    fn test_min_max() {
+        assert_eq!([3, 1, 4, 1, 5].into_iter().min_max(), Some((1, 5)));
+        assert_eq!([2].into_iter().min_max(), Some((2, 2)));
+        assert_eq!(std::iter::empty::<i32>().min_max(), None);
+    }
 }
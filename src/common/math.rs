@@ -1,4 +1,6 @@
-use glam::{IVec2, Vec2};
+use std::str::FromStr;
+
+use glam::{IVec2, IVec3, Vec2};
 
 pub trait Vec2Ext: Copy {
     fn to_direction(self) -> Self;
@@ -31,10 +33,449 @@ impl IVec2Ext for IVec2 {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+/// An axis-aligned rectangle of integer points, with both corners included.
+///
+/// `x1 <= x2` and `y1 <= y2` hold for every rectangle made by the constructors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct IRect {
     pub x1: i32,
     pub x2: i32,
     pub y1: i32,
     pub y2: i32,
 }
+
+impl IRect {
+    /// The rectangle spanned by two opposite corners, in any order.
+    pub fn from_corners(a: IVec2, b: IVec2) -> Self {
+        let (min, max) = (a.min(b), a.max(b));
+        Self {
+            x1: min.x,
+            x2: max.x,
+            y1: min.y,
+            y2: max.y,
+        }
+    }
+
+    /// The smallest rectangle containing every point, or `None` if there are no points.
+    pub fn from_points(points: impl IntoIterator<Item = IVec2>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let (min, max) = points.fold((first, first), |(min, max), point| {
+            (min.min(point), max.max(point))
+        });
+        Some(Self::from_corners(min, max))
+    }
+
+    pub fn min(self) -> IVec2 {
+        IVec2::new(self.x1, self.y1)
+    }
+
+    pub fn max(self) -> IVec2 {
+        IVec2::new(self.x2, self.y2)
+    }
+
+    pub fn width(self) -> u32 {
+        self.x2.abs_diff(self.x1) + 1
+    }
+
+    pub fn height(self) -> u32 {
+        self.y2.abs_diff(self.y1) + 1
+    }
+
+    pub fn area(self) -> u64 {
+        u64::from(self.width()) * u64::from(self.height())
+    }
+
+    pub fn contains(self, point: IVec2) -> bool {
+        self.min().cmple(point).all() && point.cmple(self.max()).all()
+    }
+
+    pub fn intersect(self, other: Self) -> Option<Self> {
+        let (min, max) = (self.min().max(other.min()), self.max().min(other.max()));
+        min.cmple(max).all().then(|| Self::from_corners(min, max))
+    }
+
+    /// The smallest rectangle containing both rectangles.
+    pub fn union(self, other: Self) -> Self {
+        Self::from_corners(self.min().min(other.min()), self.max().max(other.max()))
+    }
+
+    /// Grows the rectangle by `margin` on every side. Returns `None` if a negative margin
+    /// shrinks it to nothing.
+    pub fn expand(self, margin: i32) -> Option<Self> {
+        let (min, max) = (self.min() - margin, self.max() + margin);
+        min.cmple(max).all().then(|| Self::from_corners(min, max))
+    }
+
+    /// Every point in the rectangle, row by row.
+    pub fn points(self) -> impl Iterator<Item = IVec2> {
+        (self.y1..=self.y2).flat_map(move |y| (self.x1..=self.x2).map(move |x| IVec2::new(x, y)))
+    }
+}
+
+impl From<IVec2> for IRect {
+    fn from(point: IVec2) -> Self {
+        Self::from_corners(point, point)
+    }
+}
+
+impl From<(IVec2, IVec2)> for IRect {
+    fn from((a, b): (IVec2, IVec2)) -> Self {
+        Self::from_corners(a, b)
+    }
+}
+
+impl From<IRect> for (IVec2, IVec2) {
+    fn from(rect: IRect) -> Self {
+        (rect.min(), rect.max())
+    }
+}
+
+/// The 3-D counterpart of [`IRect`]: an axis-aligned box of integer points, with both corners
+/// included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct IBox {
+    pub x1: i32,
+    pub x2: i32,
+    pub y1: i32,
+    pub y2: i32,
+    pub z1: i32,
+    pub z2: i32,
+}
+
+impl IBox {
+    /// The box spanned by two opposite corners, in any order.
+    pub fn from_corners(a: IVec3, b: IVec3) -> Self {
+        let (min, max) = (a.min(b), a.max(b));
+        Self {
+            x1: min.x,
+            x2: max.x,
+            y1: min.y,
+            y2: max.y,
+            z1: min.z,
+            z2: max.z,
+        }
+    }
+
+    /// The smallest box containing every point, or `None` if there are no points.
+    pub fn from_points(points: impl IntoIterator<Item = IVec3>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let (min, max) = points.fold((first, first), |(min, max), point| {
+            (min.min(point), max.max(point))
+        });
+        Some(Self::from_corners(min, max))
+    }
+
+    pub fn min(self) -> IVec3 {
+        IVec3::new(self.x1, self.y1, self.z1)
+    }
+
+    pub fn max(self) -> IVec3 {
+        IVec3::new(self.x2, self.y2, self.z2)
+    }
+
+    pub fn width(self) -> u32 {
+        self.x2.abs_diff(self.x1) + 1
+    }
+
+    pub fn height(self) -> u32 {
+        self.y2.abs_diff(self.y1) + 1
+    }
+
+    pub fn depth(self) -> u32 {
+        self.z2.abs_diff(self.z1) + 1
+    }
+
+    pub fn volume(self) -> u64 {
+        u64::from(self.width()) * u64::from(self.height()) * u64::from(self.depth())
+    }
+
+    pub fn contains(self, point: IVec3) -> bool {
+        self.min().cmple(point).all() && point.cmple(self.max()).all()
+    }
+
+    pub fn intersect(self, other: Self) -> Option<Self> {
+        let (min, max) = (self.min().max(other.min()), self.max().min(other.max()));
+        min.cmple(max).all().then(|| Self::from_corners(min, max))
+    }
+
+    /// The smallest box containing both boxes.
+    pub fn union(self, other: Self) -> Self {
+        Self::from_corners(self.min().min(other.min()), self.max().max(other.max()))
+    }
+
+    /// Grows the box by `margin` on every side. Returns `None` if a negative margin shrinks it to
+    /// nothing.
+    pub fn expand(self, margin: i32) -> Option<Self> {
+        let (min, max) = (self.min() - margin, self.max() + margin);
+        min.cmple(max).all().then(|| Self::from_corners(min, max))
+    }
+
+    /// Every point in the box, ordered by z, then y, then x.
+    pub fn points(self) -> impl Iterator<Item = IVec3> {
+        (self.z1..=self.z2).flat_map(move |z| {
+            (self.y1..=self.y2)
+                .flat_map(move |y| (self.x1..=self.x2).map(move |x| IVec3::new(x, y, z)))
+        })
+    }
+}
+
+impl From<IVec3> for IBox {
+    fn from(point: IVec3) -> Self {
+        Self::from_corners(point, point)
+    }
+}
+
+impl From<(IVec3, IVec3)> for IBox {
+    fn from((a, b): (IVec3, IVec3)) -> Self {
+        Self::from_corners(a, b)
+    }
+}
+
+impl From<IBox> for (IVec3, IVec3) {
+    fn from(ibox: IBox) -> Self {
+        (ibox.min(), ibox.max())
+    }
+}
+
+/// An orthogonal direction on a grid where y grows downwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction4 {
+    /// Every direction, clockwise from up.
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// Understands `U`/`R`/`D`/`L`, `N`/`E`/`S`/`W` and the arrows `^`/`>`/`v`/`<`.
+    pub fn from_char(ch: char) -> Option<Self> {
+        match ch {
+            'U' | 'N' | '^' | '↑' => Some(Self::Up),
+            'R' | 'E' | '>' | '→' => Some(Self::Right),
+            'D' | 'S' | 'v' | '↓' => Some(Self::Down),
+            'L' | 'W' | '<' | '←' => Some(Self::Left),
+            _ => None,
+        }
+    }
+
+    pub const fn to_ivec2(self) -> IVec2 {
+        match self {
+            Self::Up => IVec2::NEG_Y,
+            Self::Right => IVec2::X,
+            Self::Down => IVec2::Y,
+            Self::Left => IVec2::NEG_X,
+        }
+    }
+
+    pub const fn turn_right(self) -> Self {
+        match self {
+            Self::Up => Self::Right,
+            Self::Right => Self::Down,
+            Self::Down => Self::Left,
+            Self::Left => Self::Up,
+        }
+    }
+
+    pub const fn turn_left(self) -> Self {
+        self.opposite().turn_right()
+    }
+
+    pub const fn opposite(self) -> Self {
+        self.turn_right().turn_right()
+    }
+
+    /// The direction of a single orthogonal step, like the one between two neighbouring cells.
+    pub fn from_ivec2(step: IVec2) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|direction| direction.to_ivec2() == step)
+    }
+
+    pub const fn arrow(self) -> char {
+        match self {
+            Self::Up => '^',
+            Self::Right => '>',
+            Self::Down => 'v',
+            Self::Left => '<',
+        }
+    }
+}
+
+impl FromStr for Direction4 {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next().and_then(Self::from_char), chars.next()) {
+            (Some(direction), None) => Ok(direction),
+            _ => Err(format!("invalid direction: {s:?}")),
+        }
+    }
+}
+
+/// An orthogonal or diagonal direction on a grid where y grows downwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// Every direction, clockwise from up.
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    pub const fn to_ivec2(self) -> IVec2 {
+        match self {
+            Self::Up => IVec2::NEG_Y,
+            Self::UpRight => IVec2::new(1, -1),
+            Self::Right => IVec2::X,
+            Self::DownRight => IVec2::ONE,
+            Self::Down => IVec2::Y,
+            Self::DownLeft => IVec2::new(-1, 1),
+            Self::Left => IVec2::NEG_X,
+            Self::UpLeft => IVec2::NEG_ONE,
+        }
+    }
+
+    /// Turns 45 degrees clockwise.
+    pub const fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Turns 45 degrees counterclockwise.
+    pub const fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    pub const fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(direction: Direction4) -> Self {
+        match direction {
+            Direction4::Up => Self::Up,
+            Direction4::Right => Self::Right,
+            Direction4::Down => Self::Down,
+            Direction4::Left => Self::Left,
+        }
+    }
+}
+
+impl FromStr for Direction8 {
+    type Err = String;
+
+    /// Understands compass points like `NE`, the arrows `↑`/`↗`/`→`/.../`↖`, and anything
+    /// [`Direction4`] does.
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "NE" | "UR" | "↗" => Ok(Self::UpRight),
+            "SE" | "DR" | "↘" => Ok(Self::DownRight),
+            "SW" | "DL" | "↙" => Ok(Self::DownLeft),
+            "NW" | "UL" | "↖" => Ok(Self::UpLeft),
+            s => s.parse::<Direction4>().map(Self::from),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_irect() {
+        let rect =
+            IRect::from_points([IVec2::new(3, -1), IVec2::new(0, 2), IVec2::new(1, 0)]).unwrap();
+        assert_eq!(
+            rect,
+            IRect::from_corners(IVec2::new(3, 2), IVec2::new(0, -1))
+        );
+        assert_eq!((rect.width(), rect.height(), rect.area()), (4, 4, 16));
+        assert!(rect.contains(IVec2::new(3, 2)));
+        assert!(!rect.contains(IVec2::new(4, 2)));
+        assert_eq!(rect.points().count() as u64, rect.area());
+        assert_eq!(rect.points().next(), Some(rect.min()));
+
+        let other = IRect::from_corners(IVec2::new(2, 2), IVec2::new(5, 5));
+        assert_eq!(
+            rect.intersect(other),
+            Some(IRect::from_corners(IVec2::new(2, 2), IVec2::new(3, 2)))
+        );
+        assert_eq!(rect.intersect(IRect::from(IVec2::new(9, 9))), None);
+        assert_eq!(
+            <(IVec2, IVec2)>::from(rect.union(other)),
+            (IVec2::new(0, -1), IVec2::new(5, 5))
+        );
+        assert_eq!(
+            IRect::from(IVec2::ZERO).expand(2),
+            Some(IRect::from_corners(IVec2::splat(-2), IVec2::splat(2)))
+        );
+        assert_eq!(rect.expand(-2), None);
+    }
+
+    #[test]
+    fn test_ibox() {
+        let ibox = IBox::from_points([IVec3::ZERO, IVec3::new(1, 2, 3)]).unwrap();
+        assert_eq!((ibox.width(), ibox.height(), ibox.depth()), (2, 3, 4));
+        assert_eq!(ibox.volume(), 24);
+        assert_eq!(ibox.points().count(), 24);
+        assert!(ibox.contains(IVec3::new(1, 1, 1)));
+        assert_eq!(
+            ibox.intersect(IBox::from_corners(IVec3::ONE, IVec3::splat(5))),
+            Some(IBox::from_corners(IVec3::ONE, IVec3::new(1, 2, 3)))
+        );
+        assert_eq!(ibox.expand(-2), None);
+    }
+
+    #[test]
+    fn test_direction4() {
+        assert_eq!("L".parse(), Ok(Direction4::Left));
+        assert_eq!(Direction4::from_char('v'), Some(Direction4::Down));
+        assert_eq!(Direction4::from_ivec2(IVec2::NEG_X), Some(Direction4::Left));
+        assert_eq!(Direction4::from_ivec2(IVec2::ONE), None);
+        assert!("LR".parse::<Direction4>().is_err());
+        assert_eq!(Direction4::Up.turn_right(), Direction4::Right);
+        assert_eq!(Direction4::Up.turn_left(), Direction4::Left);
+        assert_eq!(Direction4::Right.opposite(), Direction4::Left);
+        assert_eq!(
+            Direction4::ALL
+                .map(Direction4::to_ivec2)
+                .iter()
+                .sum::<IVec2>(),
+            IVec2::ZERO
+        );
+    }
+
+    #[test]
+    fn test_direction8() {
+        assert_eq!("NW".parse(), Ok(Direction8::UpLeft));
+        assert_eq!("^".parse(), Ok(Direction8::Up));
+        assert_eq!(Direction8::UpLeft.turn_right(), Direction8::Up);
+        assert_eq!(Direction8::Up.turn_left(), Direction8::UpLeft);
+        assert_eq!(Direction8::DownRight.opposite(), Direction8::UpLeft);
+        for direction in Direction8::ALL {
+            assert_eq!(direction.opposite().to_ivec2(), -direction.to_ivec2());
+        }
+    }
+}
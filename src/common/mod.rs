@@ -1,4 +1,17 @@
-pub mod array;
+// Some modules are toolkits for puzzles to come, so not every helper in them has a caller yet.
+#[allow(dead_code)]
+pub mod cycle;
+pub mod grid;
+pub mod image;
+pub mod interval;
+#[allow(dead_code)]
 pub mod iterator;
+#[allow(dead_code)]
 pub mod math;
+#[allow(dead_code)]
+pub mod number;
+pub mod ocr;
+pub mod parsing;
+#[allow(dead_code)]
+pub mod search;
 pub mod string;
@@ -0,0 +1,182 @@
+//! Reads the block letters that some puzzles draw as their answer.
+
+use glam::IVec2;
+
+use crate::common::grid::Grid;
+
+/// The 6 pixel high font, with every letter in a 5 pixel wide cell. Most letters are 4 pixels wide,
+/// leaving a blank column, but `Y` fills its cell and touches the next letter.
+const SMALL_FONT: Font = Font {
+    letters: "ABCEFGHIJKLOPRSUYZ",
+    cell_width: 5,
+    glyphs: "\
+.##..###...##..####.####..##..#..#.###....##.#..#.#.....##..###..###...###.#..#.#...#####.
+#..#.#..#.#..#.#....#....#..#.#..#..#......#.#.#..#....#..#.#..#.#..#.#....#..#.#...#...#.
+#..#.###..#....###..###..#....####..#......#.##...#....#..#.#..#.#..#.#....#..#..#.#...#..
+####.#..#.#....#....#....#.##.#..#..#......#.#.#..#....#..#.###..###...##..#..#...#...#...
+#..#.#..#.#..#.#....#....#..#.#..#..#...#..#.#.#..#....#..#.#....#.#.....#.#..#...#..#....
+#..#.###...##..####.#.....###.#..#.###...##..#..#.####..##..#....#..#.###...##....#..####.",
+};
+
+/// The 10 pixel high font, with letters 6 pixels wide in 8 pixel wide cells.
+const LARGE_FONT: Font = Font {
+    letters: "ABCEFGHJKLNPRXZ",
+    cell_width: 8,
+    glyphs: "\
+..##....#####....####...######..######...####...#....#.....###..#....#..#.......#....#..#####...#####...#....#..######
+.#..#...#....#..#....#..#.......#.......#....#..#....#......#...#...#...#.......##...#..#....#..#....#..#....#.......#
+#....#..#....#..#.......#.......#.......#.......#....#......#...#..#....#.......##...#..#....#..#....#...#..#........#
+#....#..#....#..#.......#.......#.......#.......#....#......#...#.#.....#.......#.#..#..#....#..#....#...#..#.......#.
+#....#..#####...#.......#####...#####...#.......######......#...##......#.......#.#..#..#####...#####.....##.......#..
+######..#....#..#.......#.......#.......#..###..#....#......#...##......#.......#..#.#..#.......#..#......##......#...
+#....#..#....#..#.......#.......#.......#....#..#....#......#...#.#.....#.......#..#.#..#.......#...#....#..#....#....
+#....#..#....#..#.......#.......#.......#....#..#....#..#...#...#..#....#.......#...##..#.......#...#....#..#...#.....
+#....#..#....#..#....#..#.......#.......#...##..#....#..#...#...#...#...#.......#...##..#.......#....#..#....#..#.....
+#....#..#####....####...######..#........###.#..#....#...###....#....#..######..#....#..#.......#....#..#....#..######",
+};
+
+/// Letters drawn side by side in `glyphs`, in the same order as in `letters`.
+struct Font {
+    letters: &'static str,
+    /// The columns taken up by each letter, including the gap to the next one.
+    cell_width: usize,
+    glyphs: &'static str,
+}
+
+/// Reads the letters drawn by the lit pixels, in either font.
+///
+/// Blank rows and columns around the letters are ignored. Returns `None` if the picture is empty
+/// or anything in it isn't a known letter.
+pub fn read_letters(picture: &Grid<bool>) -> Option<String> {
+    let lit_rows = (0..picture.height())
+        .filter(|&y| picture.row(y).contains(&true))
+        .collect::<Vec<_>>();
+    let font = match lit_rows.last()? - lit_rows.first()? + 1 {
+        6 => SMALL_FONT,
+        10 => LARGE_FONT,
+        _ => return None,
+    };
+    let font_glyphs = split_glyphs(&parse_picture(font.glyphs)?, font.cell_width, 0);
+    // The first letter might not reach the left edge of its cell, so try every alignment.
+    (0..font.cell_width).find_map(|offset| {
+        split_glyphs(picture, font.cell_width, offset)
+            .iter()
+            .map(|glyph| {
+                let index = font_glyphs.iter().position(|known| known == glyph)?;
+                font.letters.chars().nth(index)
+            })
+            .collect()
+    })
+}
+
+fn parse_picture(picture: &str) -> Option<Grid<bool>> {
+    let pixel = |ch| match ch {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    };
+    Grid::parse(picture, "pixel", pixel).ok()
+}
+
+/// Cuts the picture into cells `cell_width` columns wide, the first one starting `offset` columns
+/// before the first lit column. Each cell is cropped to its lit pixels, so letters can be compared
+/// wherever they sit in their cell. Letters can touch, so the gaps between them can't be relied on.
+fn split_glyphs(picture: &Grid<bool>, cell_width: usize, offset: usize) -> Vec<Grid<bool>> {
+    let is_lit = |pos: IVec2| picture.get(pos).copied().unwrap_or_default();
+    let lit = picture
+        .iter()
+        .filter(|&(_, &lit)| lit)
+        .map(|(pos, _)| pos)
+        .collect::<Vec<_>>();
+    let (Some(min), Some(max)) = (
+        lit.iter().copied().reduce(IVec2::min),
+        lit.iter().copied().reduce(IVec2::max),
+    ) else {
+        return Vec::new();
+    };
+    let height = (max.y - min.y + 1) as usize;
+
+    let mut glyphs = Vec::new();
+    let mut left = min.x - offset as i32;
+    while left <= max.x {
+        let columns = (left..left + cell_width as i32)
+            .filter(|&x| (min.y..=max.y).any(|y| is_lit(IVec2::new(x, y))))
+            .collect::<Vec<_>>();
+        let offset = IVec2::new(columns.first().copied().unwrap_or(left), min.y);
+        let width = columns
+            .last()
+            .map_or(0, |last| (last - offset.x + 1) as usize);
+        glyphs.push(Grid::from_fn(width, height, |pos| is_lit(pos + offset)));
+        left += cell_width as i32;
+    }
+    glyphs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Like [`read_letters`], for a picture drawn with `#` for lit pixels and `.` for dark ones.
+    fn read_letters_str(picture: &str) -> Option<String> {
+        read_letters(&parse_picture(picture)?)
+    }
+
+    #[test]
+    fn test_small_font() {
+        // A 40x6 screen, with every letter in a 5 pixel wide cell.
+        let picture = "\
+###..#....####.####.#..#.#....###..###..
+#..#.#....#....#....#..#.#....#..#.#..#.
+#..#.#....###..###..#..#.#....#..#.###..
+###..#....#....#....#..#.#....###..#..#.
+#....#....#....#....#..#.#....#....#..#.
+#....####.####.#.....##..####.#....###..";
+        assert_eq!(read_letters_str(picture).as_deref(), Some("PLEFULPB"));
+    }
+
+    #[test]
+    fn test_touching_letters() {
+        // `Y` fills its whole cell, so there's no blank column between it and the next letter.
+        let picture = "\
+#...#.##..####.
+#...##..#....#.
+.#.#.#..#...#..
+..#..####..#...
+..#..#..#.#....
+..#..#..#.####.";
+        assert_eq!(read_letters_str(picture).as_deref(), Some("YAZ"));
+        let picture = "\
+#..#.#...#.##..
+#..#.#...##..#.
+####..#.#.#..#.
+#..#...#..####.
+#..#...#..#..#.
+#..#...#..#..#.";
+        assert_eq!(read_letters_str(picture).as_deref(), Some("HYA"));
+    }
+
+    #[test]
+    fn test_large_font() {
+        let picture = "\
+........................
+..#####...#....#...####.
+..#....#..##...#..#....#
+..#....#..##...#..#.....
+..#....#..#.#..#..#.....
+..#####...#.#..#..#.....
+..#..#....#..#.#..#..###
+..#...#...#..#.#..#....#
+..#...#...#...##..#....#
+..#....#..#...##..#...##
+..#....#..#....#...###.#";
+        assert_eq!(read_letters_str(picture).as_deref(), Some("RNG"));
+    }
+
+    #[test]
+    fn test_unknown() {
+        assert_eq!(read_letters_str("#..\n#..\n..."), None);
+        assert_eq!(read_letters_str("....\n...."), None);
+        let not_letters = "##..##..\n###...##\n####....\n#####...\n######..\n#######.";
+        assert_eq!(read_letters_str(not_letters), None);
+    }
+}
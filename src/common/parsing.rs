@@ -0,0 +1,43 @@
+use nom::IResult;
+use serde::de::DeserializeOwned;
+
+use crate::error::{Error, Result};
+
+/// Runs a nom parser over the whole of `input`. Only trailing whitespace may be left over.
+pub fn complete<'a, T>(
+    input: &'a str,
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, T>,
+) -> Result<T> {
+    match parser(input) {
+        Ok((rest, output)) => {
+            let rest_trimmed = rest.trim_start();
+            if rest_trimmed.is_empty() {
+                Ok(output)
+            } else {
+                Err(Error::parse(
+                    input,
+                    rest_trimmed,
+                    "unexpected trailing input",
+                ))
+            }
+        }
+        Err(nom::Err::Error(err) | nom::Err::Failure(err)) => Err(Error::parse(
+            input,
+            err.input,
+            format!("unexpected input ({})", err.code.description()),
+        )),
+        Err(nom::Err::Incomplete(_)) => Err(Error::parse(
+            input,
+            &input[input.len()..],
+            "unexpected end of input",
+        )),
+    }
+}
+
+/// Deserializes a TOML document, reporting errors at their location in `input`.
+pub fn toml<T: DeserializeOwned>(input: &str) -> Result<T> {
+    ::toml::from_str(input).map_err(|err| {
+        let at = err.span().map_or(0, |span| span.start);
+        Error::parse(input, input.get(at..).unwrap_or_default(), err.message())
+    })
+}
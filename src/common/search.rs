@@ -0,0 +1,238 @@
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// A way from a start to a goal, found by one of the searches in this module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route<N, C> {
+    /// The nodes along the route, from the start to the goal, both included.
+    pub nodes: Vec<N>,
+    pub cost: C,
+}
+
+impl<N, C> Route<N, C> {
+    pub fn start(&self) -> &N {
+        &self.nodes[0]
+    }
+
+    pub fn goal(&self) -> &N {
+        &self.nodes[self.nodes.len() - 1]
+    }
+}
+
+/// Finds a route with the fewest steps from any of `starts` to a node for which `is_goal` holds.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Route<N, usize>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents = HashMap::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if let Entry::Vacant(entry) = parents.entry(start.clone()) {
+            entry.insert(None);
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((node, steps)) = queue.pop_front() {
+        if is_goal(&node) {
+            return Some(Route {
+                nodes: unwind(&parents, node),
+                cost: steps,
+            });
+        }
+        for successor in successors(&node) {
+            if let Entry::Vacant(entry) = parents.entry(successor.clone()) {
+                entry.insert(Some(node.clone()));
+                queue.push_back((successor, steps + 1));
+            }
+        }
+    }
+    None
+}
+
+/// The fewest steps from any of `starts` to every node that can be reached from them.
+pub fn bfs_distances<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if let Entry::Vacant(entry) = distances.entry(start.clone()) {
+            entry.insert(0);
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((node, steps)) = queue.pop_front() {
+        for successor in successors(&node) {
+            if let Entry::Vacant(entry) = distances.entry(successor.clone()) {
+                entry.insert(steps + 1);
+                queue.push_back((successor, steps + 1));
+            }
+        }
+    }
+    distances
+}
+
+/// Finds a cheapest route from any of `starts` to a node for which `is_goal` holds.
+/// `successors` returns the neighbours of a node with the cost of moving to each of them.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Route<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, successors, |_| C::default(), is_goal)
+}
+
+/// Like [`dijkstra`], but explores nodes that `heuristic` estimates to be closer to a goal first.
+/// The route is only guaranteed to be cheapest if `heuristic` never overestimates.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Route<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    // Nodes are kept in `nodes` and referred to by index, so the heap doesn't need `N: Ord`.
+    let mut nodes = Vec::new();
+    let mut best: HashMap<N, (usize, C, Option<usize>)> = HashMap::new();
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        if let Entry::Vacant(entry) = best.entry(start.clone()) {
+            entry.insert((nodes.len(), C::default(), None));
+            heap.push(Reverse((heuristic(&start), C::default(), nodes.len())));
+            nodes.push(start);
+        }
+    }
+
+    while let Some(Reverse((_, cost, index))) = heap.pop() {
+        let node = nodes[index].clone();
+        if cost > best[&node].1 {
+            continue;
+        }
+        if is_goal(&node) {
+            let mut route = vec![node];
+            let mut parent = best[&route[0]].2;
+            while let Some(index) = parent {
+                parent = best[&nodes[index]].2;
+                route.push(nodes[index].clone());
+            }
+            route.reverse();
+            return Some(Route { nodes: route, cost });
+        }
+
+        for (successor, step_cost) in successors(&node) {
+            let new_cost = cost + step_cost;
+            let successor_index = match best.entry(successor.clone()) {
+                Entry::Occupied(mut entry) => {
+                    let (successor_index, old_cost, parent) = entry.get_mut();
+                    if new_cost >= *old_cost {
+                        continue;
+                    }
+                    *old_cost = new_cost;
+                    *parent = Some(index);
+                    *successor_index
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert((nodes.len(), new_cost, Some(index)));
+                    nodes.push(successor.clone());
+                    nodes.len() - 1
+                }
+            };
+            heap.push(Reverse((
+                new_cost + heuristic(&successor),
+                new_cost,
+                successor_index,
+            )));
+        }
+    }
+    None
+}
+
+/// Follows the parents back from `node` to a start and returns the nodes in start-to-`node` order.
+fn unwind<N: Clone + Eq + Hash>(parents: &HashMap<N, Option<N>>, node: N) -> Vec<N> {
+    let mut nodes = vec![node];
+    while let Some(Some(parent)) = parents.get(&nodes[nodes.len() - 1]) {
+        nodes.push(parent.clone());
+    }
+    nodes.reverse();
+    nodes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A small weighted graph where 0 -> 1 is the cheaper first step, but 0 -> 2 -> 3 the cheaper route.
+    fn edges(node: &u32) -> Vec<(u32, u32)> {
+        match node {
+            0 => vec![(1, 1), (2, 5)],
+            1 => vec![(3, 10)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        let route = bfs([0], |&n: &i32| [n + 1, n * 2], |&n| n == 10).unwrap();
+        assert_eq!(route.cost, 5);
+        assert_eq!(route.nodes, [0, 1, 2, 4, 5, 10]);
+        assert_eq!((*route.start(), *route.goal()), (0, 10));
+        assert!(bfs([0], |_: &i32| [], |&n| n == 1).is_none());
+    }
+
+    #[test]
+    fn test_bfs_multiple_starts() {
+        let route = bfs([0, 7], |&n: &i32| [n + 1], |&n| n == 9).unwrap();
+        assert_eq!(route.nodes, [7, 8, 9]);
+    }
+
+    #[test]
+    fn test_bfs_distances() {
+        let distances = bfs_distances([0], |&n: &i32| {
+            [n + 1, n * 2].into_iter().filter(|&n| n <= 5)
+        });
+        assert_eq!(distances.len(), 6);
+        assert_eq!(distances[&0], 0);
+        assert_eq!(distances[&4], 3);
+        assert_eq!(distances[&5], 4);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let route = dijkstra([0], edges, |&n| n == 3).unwrap();
+        assert_eq!(route.nodes, [0, 2, 3]);
+        assert_eq!(route.cost, 6);
+    }
+
+    #[test]
+    fn test_astar() {
+        let route = astar([0], edges, |&n| 3 - n.min(3), |&n| n == 3).unwrap();
+        assert_eq!(route.nodes, [0, 2, 3]);
+        assert_eq!(route.cost, 6);
+    }
+}
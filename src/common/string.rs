@@ -1,8 +1,9 @@
-pub fn ascii_char_at(s: &str, index: usize) -> char {
-    if !s.is_ascii() {
-        panic!("String is not a valid ASCII string");
-    }
-    s.as_bytes()[index] as char
+/// Returns `None` if `index` is out of bounds or doesn't point at an ASCII character.
+pub fn ascii_char_at(s: &str, index: usize) -> Option<char> {
+    s.as_bytes()
+        .get(index)
+        .filter(|byte| byte.is_ascii())
+        .map(|&byte| byte as char)
 }
 
 pub fn alphabet_score(ch: char) -> u32 {
@@ -19,6 +20,13 @@ pub fn alphabet_score(ch: char) -> u32 {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_ascii_char_at() {
+        assert_eq!(ascii_char_at("abc", 1), Some('b'));
+        assert_eq!(ascii_char_at("abc", 3), None);
+        assert_eq!(ascii_char_at("aé", 1), None);
+    }
+
     #[test]
     fn test_alphbet_score() {
         assert_eq!(alphabet_score('a'), 1);
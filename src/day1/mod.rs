@@ -1,27 +1,60 @@
+use crate::{
+    common::iterator::IteratorExt,
+    error::{Error, Result},
+    solution::Solution,
+};
+
 type Calories = i32;
 
-pub fn solve_part_one(input: String) -> String {
-    let elves_calories = calculate_elves_calories(input);
-    elves_calories.into_iter().max().unwrap().to_string()
-}
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u32 = 1;
+
+    type Input<'a> = Vec<Calories>;
+    type Params = ();
+
+    const PARAMS: Self::Params = ();
+    const EXAMPLE_PARAMS: Self::Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        calculate_elves_calories(input)
+    }
+
+    fn solve_part_one(elves_calories: Self::Input<'_>, _: Self::Params) -> Result<String> {
+        elves_calories
+            .into_iter()
+            .max()
+            .map(|calories| calories.to_string())
+            .ok_or_else(|| Error::unsolvable("there are no elves"))
+    }
 
-pub fn solve_part_two(input: String) -> String {
-    let mut elves_calories = calculate_elves_calories(input);
-    elves_calories.sort_unstable();
-    let length = elves_calories.len();
-    elves_calories[(length - 3)..length]
-        .iter()
-        .sum::<Calories>()
-        .to_string()
+    fn solve_part_two(elves_calories: Self::Input<'_>, _: Self::Params) -> Result<String> {
+        let length = elves_calories.len();
+        if length < 3 {
+            return Err(Error::unsolvable(format!(
+                "need at least 3 elves, found {length}"
+            )));
+        }
+        Ok(elves_calories
+            .into_iter()
+            .top_k(3)
+            .into_iter()
+            .sum::<Calories>()
+            .to_string())
+    }
 }
 
-fn calculate_elves_calories(input: String) -> Vec<Calories> {
+fn calculate_elves_calories(input: &str) -> Result<Vec<Calories>> {
     input
         .split("\n\n")
         .map(|elf_load| {
             elf_load
                 .lines()
-                .map(|item| item.parse::<Calories>().unwrap())
+                .map(|item| {
+                    item.parse::<Calories>()
+                        .map_err(|err| Error::parse(input, item, format!("{err}: {item:?}")))
+                })
                 .sum()
         })
         .collect()
@@ -1,57 +1,96 @@
-use crate::common::array::to_2d_index;
+use glam::IVec2;
 
-pub fn solve_part_one(input: String) -> String {
-    const IMPORTANT_CYCLES: [i32; 6] = [20, 60, 100, 140, 180, 220];
+use crate::{
+    common::{grid::Grid, ocr},
+    error::{Error, Result},
+    solution::Solution,
+};
 
-    let mut cycles = 0;
-    let mut x_register = 1;
-    let mut signal_strengths = Vec::new();
+pub struct Day10;
 
-    for instruction in parse_input(&input) {
-        let mut instruction_cycles = instruction.num_cycles();
-        while instruction_cycles > 0 {
-            cycles += 1;
-            if IMPORTANT_CYCLES.contains(&cycles) {
-                signal_strengths.push(cycles * x_register);
-            }
-            if instruction_cycles == 1 {
-                match instruction {
-                    Instruction::Addx(num) => x_register += num,
-                    Instruction::Noop => (),
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+
+    type Input<'a> = Vec<Instruction>;
+    type Params = Screen;
+
+    const PARAMS: Self::Params = Screen::Letters;
+    // The example draws stripes rather than letters.
+    const EXAMPLE_PARAMS: Self::Params = Screen::Picture;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn solve_part_one(instructions: Self::Input<'_>, _: Self::Params) -> Result<String> {
+        const IMPORTANT_CYCLES: [i32; 6] = [20, 60, 100, 140, 180, 220];
+
+        let mut cycles = 0;
+        let mut x_register = 1;
+        let mut signal_strengths = Vec::new();
+
+        for instruction in instructions {
+            let mut instruction_cycles = instruction.num_cycles();
+            while instruction_cycles > 0 {
+                cycles += 1;
+                if IMPORTANT_CYCLES.contains(&cycles) {
+                    signal_strengths.push(cycles * x_register);
                 }
+                if instruction_cycles == 1 {
+                    match instruction {
+                        Instruction::Addx(num) => x_register += num,
+                        Instruction::Noop => (),
+                    }
+                }
+                instruction_cycles -= 1;
             }
-            instruction_cycles -= 1;
+        }
+
+        Ok(signal_strengths.into_iter().sum::<i32>().to_string())
+    }
+
+    fn solve_part_two(instructions: Self::Input<'_>, screen: Self::Params) -> Result<String> {
+        let pixels = draw_screen(&instructions);
+        let picture = pixels.map(|&lit| if lit { '#' } else { '.' }).to_string();
+        match screen {
+            Screen::Letters => ocr::read_letters(&pixels).ok_or_else(|| {
+                Error::unsolvable(format!("can't read the letters on the screen:\n{picture}"))
+            }),
+            Screen::Picture => Ok(picture),
         }
     }
+}
 
-    signal_strengths.into_iter().sum::<i32>().to_string()
+/// What part two answers with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Screen {
+    /// The letters drawn on the screen, ready to submit.
+    Letters,
+    /// The screen itself, one line per row with `#` for lit pixels.
+    Picture,
 }
 
-pub fn solve_part_two(input: String) -> String {
-    const CRT_WIDTH: usize = 40;
+pub fn draw_screen(instructions: &[Instruction]) -> Grid<bool> {
+    const CRT_WIDTH: i32 = 40;
+    const CRT_HEIGHT: i32 = 6;
 
     let mut cycles = 0;
-    let mut sprite_x = 1;
-    let mut screen = String::new();
+    let mut sprite_x: i32 = 1;
+    let mut screen = Grid::filled(CRT_WIDTH as usize, CRT_HEIGHT as usize, false);
 
-    for instruction in parse_input(&input) {
+    for instruction in instructions {
         let mut instruction_cycles = instruction.num_cycles();
         while instruction_cycles > 0 {
+            let position = IVec2::new(cycles % CRT_WIDTH, cycles / CRT_WIDTH);
             cycles += 1;
-            let (x_position, _) = to_2d_index(cycles - 1, CRT_WIDTH as usize);
-            let ch = if (sprite_x - 1..=sprite_x + 1).contains(&x_position) {
-                '#'
-            } else {
-                '.'
-            };
-            screen.push(ch);
-            let reached_newline = cycles % 40 == 0;
-            if reached_newline {
-                screen.push('\n');
+            if (sprite_x - 1..=sprite_x + 1).contains(&position.x) {
+                if let Some(pixel) = screen.get_mut(position) {
+                    *pixel = true;
+                }
             }
             if instruction_cycles == 1 {
                 match instruction {
-                    Instruction::Addx(num) => sprite_x = (sprite_x as i32 + num) as usize,
+                    Instruction::Addx(num) => sprite_x += num,
                     Instruction::Noop => (),
                 }
             }
@@ -61,7 +100,7 @@ pub fn solve_part_two(input: String) -> String {
     screen
 }
 
-enum Instruction {
+pub enum Instruction {
     Addx(i32),
     Noop,
 }
@@ -70,15 +109,20 @@ impl Instruction {
     pub const ADDX_NUM_CYCLES: u32 = 2;
     pub const NOOP_NUM_CYCLES: u32 = 1;
 
-    pub fn from_input(input: &str) -> Self {
-        let instruction_name = &input[0..4];
-        match instruction_name {
-            "noop" => Self::Noop,
-            "addx" => {
-                let count = &input[5..];
-                Self::Addx(count.parse().unwrap())
-            }
-            _ => unreachable!(),
+    pub fn from_input(input: &str, line: &str) -> Result<Self> {
+        if line == "noop" {
+            Ok(Self::Noop)
+        } else if let Some(count) = line.strip_prefix("addx ") {
+            let count = count
+                .parse()
+                .map_err(|err| Error::parse(input, count, format!("{err}: {count:?}")))?;
+            Ok(Self::Addx(count))
+        } else {
+            Err(Error::parse(
+                input,
+                line,
+                format!("unknown instruction: {line}"),
+            ))
         }
     }
 
@@ -90,6 +134,9 @@ impl Instruction {
     }
 }
 
-fn parse_input(input: &str) -> Vec<Instruction> {
-    input.lines().map(Instruction::from_input).collect()
+fn parse_input(input: &str) -> Result<Vec<Instruction>> {
+    input
+        .lines()
+        .map(|line| Instruction::from_input(input, line))
+        .collect()
 }
@@ -1,33 +1,60 @@
+use std::{fmt, str::FromStr};
+
 use itertools::Itertools;
 
-pub fn solve_part_one(input: String) -> String {
-    const NUM_ROUNDS: u64 = 20;
-    solve_common(input, NUM_ROUNDS, true)
-}
+use crate::{
+    common::{iterator::IteratorExt, number::lcm},
+    error::{Error, Result},
+    solution::Solution,
+};
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+
+    type Input<'a> = Vec<Monkey>;
+    type Params = ();
 
-pub fn solve_part_two(input: String) -> String {
-    const NUM_ROUNDS: u64 = 10_000;
-    solve_common(input, NUM_ROUNDS, false)
+    const PARAMS: Self::Params = ();
+    const EXAMPLE_PARAMS: Self::Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn solve_part_one(monkeys: Self::Input<'_>, _: Self::Params) -> Result<String> {
+        const NUM_ROUNDS: u64 = 20;
+        solve_common(monkeys, NUM_ROUNDS, true)
+    }
+
+    fn solve_part_two(monkeys: Self::Input<'_>, _: Self::Params) -> Result<String> {
+        const NUM_ROUNDS: u64 = 10_000;
+        solve_common(monkeys, NUM_ROUNDS, false)
+    }
 }
 
-fn solve_common(input: String, num_rounds: u64, relief: bool) -> String {
-    let mut monkeys = parse_input(&input);
-    let chinese_remainder = calc_chinese_remainder(&monkeys);
+fn solve_common(mut monkeys: Vec<Monkey>, num_rounds: u64, relief: bool) -> Result<String> {
+    // Dividing by 3 doesn't commute with the modulus, so relief needs the exact levels.
+    let modulus = if relief {
+        None
+    } else {
+        Some(calc_worry_modulus(&monkeys)?)
+    };
     for _ in 0..num_rounds {
         for monkey_idx in 0..monkeys.len() {
             for _ in 0..monkeys[monkey_idx].items.len() {
-                let (item, next_monkey_idx) =
-                    monkeys[monkey_idx].inspect_item(relief, chinese_remainder);
+                let (item, next_monkey_idx) = monkeys[monkey_idx].inspect_item(relief, modulus)?;
                 monkeys[next_monkey_idx].items.push(item);
             }
         }
     }
 
-    calc_monkey_business(&monkeys).to_string()
+    calc_monkey_business(&monkeys).map(|monkey_business| monkey_business.to_string())
 }
 
 #[derive(Debug)]
-struct Monkey {
+pub struct Monkey {
     pub items: Vec<WorryLevel>,
     operation: Operation,
     divisible_by: WorryLevel,
@@ -43,41 +70,73 @@ impl Monkey {
     //   Test: divisible by 23
     //     If true: throw to monkey 2
     //     If false: throw to monkey 3
-    pub fn from_input(input: &str) -> Self {
-        let mut lines = input.lines().skip(1);
-        let starting_items = lines.next().unwrap()[18..]
+    pub fn from_input(input: &str, block: &str, num_monkeys: usize) -> Result<Self> {
+        let mut lines = block.lines().skip(1);
+        let mut field = |prefix: &str| {
+            let line = lines
+                .next()
+                .ok_or_else(|| Error::parse(input, block, format!("missing `{prefix}` line")))?;
+            line.trim_start()
+                .strip_prefix(prefix)
+                .ok_or_else(|| Error::parse(input, line, format!("expected `{prefix}`")))
+        };
+
+        let starting_items = field("Starting items: ")?
             .split(", ")
-            .map(|s| s.parse().unwrap())
-            .collect_vec();
+            .map(|item| parse_number(input, item))
+            .collect::<Result<Vec<_>>>()?;
 
-        let operation = Operation::from_input(&lines.next().unwrap()[19..]);
-        let divisible_by = lines.next().unwrap()[21..].parse().unwrap();
+        let operation = Operation::from_input(input, field("Operation: new = ")?)?;
+        let divisible_by_field = field("Test: divisible by ")?;
+        let divisible_by = parse_number(input, divisible_by_field)?;
+        if divisible_by == 0 {
+            return Err(Error::parse(
+                input,
+                divisible_by_field,
+                "can't divide by zero",
+            ));
+        }
 
-        let to_throw_if_true = lines.next().unwrap()[29..].parse().unwrap();
-        let to_throw_if_false = lines.next().unwrap()[30..].parse().unwrap();
+        let parse_target = |target: &str| {
+            let idx: usize = parse_number(input, target)?;
+            if idx >= num_monkeys {
+                return Err(Error::parse(
+                    input,
+                    target,
+                    format!("there are only {num_monkeys} monkeys"),
+                ));
+            }
+            Ok(idx)
+        };
+        let to_throw_if_true = parse_target(field("If true: throw to monkey ")?)?;
+        let to_throw_if_false = parse_target(field("If false: throw to monkey ")?)?;
 
-        Self {
+        Ok(Self {
             items: starting_items,
             operation,
             divisible_by,
             to_throw_if_true,
             to_throw_if_false,
             num_inspections: 0,
-        }
+        })
     }
 
-    pub fn inspect_item(&mut self, relief: bool, chinese_remainder: u64) -> (WorryLevel, usize) {
+    /// Worry levels are kept modulo `modulus` if there is one, and must stay exact otherwise.
+    pub fn inspect_item(
+        &mut self,
+        relief: bool,
+        modulus: Option<WorryLevel>,
+    ) -> Result<(WorryLevel, usize)> {
         let item = self.items.remove(0);
-        let item = self.operation.calc_new_priority(item);
+        let item = self.operation.calc_new_priority(item, modulus)?;
         let item = if relief { item / 3 } else { item };
-        let item = item % chinese_remainder;
         self.num_inspections += 1;
-        let next_idx = if item % self.divisible_by == 0 {
+        let next_idx = if item.is_multiple_of(self.divisible_by) {
             self.to_throw_if_true
         } else {
             self.to_throw_if_false
         };
-        (item, next_idx)
+        Ok((item, next_idx))
     }
 }
 
@@ -90,26 +149,53 @@ struct Operation {
 }
 
 impl Operation {
-    pub fn from_input(input: &str) -> Self {
-        let mut parts = input.split_ascii_whitespace().skip(1);
-        let operation_type = OperationType::from_input(parts.next().unwrap());
-        let rhs = Value::from_input(parts.next().unwrap());
-        Self {
+    pub fn from_input(input: &str, expression: &str) -> Result<Self> {
+        let Some(("old", operation_type, rhs)) =
+            expression.split_ascii_whitespace().collect_tuple()
+        else {
+            return Err(Error::parse(
+                input,
+                expression,
+                "expected an operation like `old * 19`",
+            ));
+        };
+        let operation_type = OperationType::from_input(operation_type).ok_or_else(|| {
+            Error::parse(
+                input,
+                operation_type,
+                format!("unknown operator: {operation_type}"),
+            )
+        })?;
+        let rhs = Value::from_input(input, rhs)?;
+        Ok(Self {
             operation_type,
             rhs,
-        }
+        })
     }
 
-    pub fn calc_new_priority(&self, item: WorryLevel) -> WorryLevel {
+    /// Calculated in a `u128`, which can't overflow, before taking the `modulus`. Without one, the
+    /// new level has to fit in a `WorryLevel`.
+    pub fn calc_new_priority(
+        &self,
+        item: WorryLevel,
+        modulus: Option<WorryLevel>,
+    ) -> Result<WorryLevel> {
         let rhs = match self.rhs {
             Value::Old => item,
             Value::New(new) => new,
         };
 
-        match self.operation_type {
+        let (item, rhs) = (u128::from(item), u128::from(rhs));
+        let new = match self.operation_type {
             OperationType::Add => item + rhs,
             OperationType::Mul => item * rhs,
-        }
+        };
+        let new = match modulus {
+            Some(modulus) => new % u128::from(modulus),
+            None => new,
+        };
+        WorryLevel::try_from(new)
+            .map_err(|_| Error::unsolvable(format!("a worry level of {new} is too large to track")))
     }
 }
 
@@ -120,11 +206,11 @@ enum OperationType {
 }
 
 impl OperationType {
-    pub fn from_input(input: &str) -> Self {
+    pub fn from_input(input: &str) -> Option<Self> {
         match input {
-            "+" => OperationType::Add,
-            "*" => OperationType::Mul,
-            _ => unreachable!(),
+            "+" => Some(OperationType::Add),
+            "*" => Some(OperationType::Mul),
+            _ => None,
         }
     }
 }
@@ -136,29 +222,90 @@ enum Value {
 }
 
 impl Value {
-    pub fn from_input(input: &str) -> Self {
-        match input {
-            "old" => Value::Old,
-            num => Value::New(num.parse().unwrap()),
+    pub fn from_input(input: &str, value: &str) -> Result<Self> {
+        match value {
+            "old" => Ok(Value::Old),
+            num => parse_number(input, num).map(Value::New),
         }
     }
 }
 
-fn parse_input(input: &str) -> Vec<Monkey> {
-    input.split("\n\n").map(Monkey::from_input).collect()
+fn parse_input(input: &str) -> Result<Vec<Monkey>> {
+    let blocks = input.split("\n\n").collect_vec();
+    blocks
+        .iter()
+        .map(|block| Monkey::from_input(input, block, blocks.len()))
+        .collect()
+}
+
+fn parse_number<T: FromStr>(input: &str, number: &str) -> Result<T>
+where
+    T::Err: fmt::Display,
+{
+    number
+        .parse()
+        .map_err(|err| Error::parse(input, number, format!("{err}: {number:?}")))
 }
 
-fn calc_monkey_business(monkeys: &[Monkey]) -> u64 {
-    let mut activity = monkeys
+fn calc_monkey_business(monkeys: &[Monkey]) -> Result<u64> {
+    if monkeys.len() < 2 {
+        return Err(Error::unsolvable(format!(
+            "need at least 2 monkeys, found {}",
+            monkeys.len()
+        )));
+    }
+    Ok(monkeys
         .iter()
         .map(|monkey| monkey.num_inspections)
-        .collect_vec();
-    activity.sort_unstable();
-    activity[activity.len() - 2..activity.len()]
+        .top_k(2)
+        .into_iter()
+        .product())
+}
+
+/// The lcm of the monkeys' divisors, the smallest modulus that keeps every test's outcome.
+fn calc_worry_modulus(monkeys: &[Monkey]) -> Result<WorryLevel> {
+    monkeys
         .iter()
-        .product()
+        .map(|monkey| monkey.divisible_by)
+        .try_fold(1, lcm)
+        .ok_or_else(|| Error::unsolvable("the lcm of the divisors is too large"))
 }
 
-fn calc_chinese_remainder(monkeys: &[Monkey]) -> u64 {
-    monkeys.iter().map(|monkey| monkey.divisible_by).product()
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{InputKind, Part};
+
+    /// The divisors share factors and have an lcm of 3e10, so squaring a level overflows a `u64`.
+    const LARGE_DIVISORS: &str = "\
+Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * old
+  Test: divisible by 6000000000
+    If true: throw to monkey 1
+    If false: throw to monkey 2
+
+Monkey 1:
+  Starting items: 54, 65
+  Operation: new = old + 6000000001
+  Test: divisible by 10000000000
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 29999999999
+  Operation: new = old * 19
+  Test: divisible by 15000000000
+    If true: throw to monkey 0
+    If false: throw to monkey 1";
+
+    #[test]
+    fn test_large_divisors() {
+        assert_eq!(
+            Day11::solve(LARGE_DIVISORS, Part::Two, InputKind::Example).unwrap(),
+            "625000000"
+        );
+        // With relief, the squared levels grow far beyond what can be tracked.
+        assert!(Day11::solve(LARGE_DIVISORS, Part::One, InputKind::Example).is_err());
+    }
 }
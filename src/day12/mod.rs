@@ -1,126 +1,177 @@
-use itertools::Itertools;
-use petgraph::prelude::*;
-
-use petgraph::algo::dijkstra;
-
-use crate::common::string::alphabet_score;
-
-pub fn solve_part_one(input: String) -> String {
-    let mut graph = DiGraph::new();
-    let mut start_node = NodeIndex::default();
-    let mut finish_node = NodeIndex::default();
-    let matrix: Vec<Vec<NodeIndex>> = input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|ch| {
-                    let node = graph.add_node(ch);
-                    match ch {
-                        'S' => start_node = node,
-                        'E' => finish_node = node,
-                        _ => (),
-                    }
-                    node
-                })
-                .collect()
-        })
-        .collect();
+use std::io::{self, Write};
 
-    let h = matrix.len();
-    let w = matrix[0].len();
-    for (x, y) in (0..w).cartesian_product(0..h) {
-        let node = matrix[y][x];
-        let ch = graph[node];
+use glam::IVec2;
 
-        let mut adjacent_indeces = Vec::new();
-        if x > 0 {
-            adjacent_indeces.push((x - 1, y));
-        }
-        if y > 0 {
-            adjacent_indeces.push((x, y - 1));
-        }
-        if x < w - 1 {
-            adjacent_indeces.push((x + 1, y));
-        }
-        if y < h - 1 {
-            adjacent_indeces.push((x, y + 1));
-        }
+use crate::{
+    common::{
+        grid::Grid,
+        image::{self, Rgb},
+        math::Direction4,
+        search::{bfs, bfs_distances, Route},
+        string::alphabet_score,
+    },
+    error::{Error, Result},
+    solution::Solution,
+};
 
-        for (adj_x, adj_y) in adjacent_indeces {
-            let adjacent_node = matrix[adj_y][adj_x];
-            let adjacent_ch = graph[adjacent_node];
-            if !graph.contains_edge(node, adjacent_node)
-                && char_to_height(ch) + 1 >= char_to_height(adjacent_ch)
-            {
-                graph.add_edge(node, adjacent_node, ());
-            }
-        }
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u32 = 12;
+
+    type Input<'a> = HeightMap;
+    type Params = ();
+
+    const PARAMS: Self::Params = ();
+    const EXAMPLE_PARAMS: Self::Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        HeightMap::from_input(input)
     }
 
-    let distances = dijkstra(&graph, start_node, Some(finish_node), |_| 1);
-    distances[&finish_node].to_string()
+    fn solve_part_one(height_map: Self::Input<'_>, _: Self::Params) -> Result<String> {
+        height_map
+            .shortest_climb()
+            .map(|route| route.cost.to_string())
+            .ok_or_else(|| Error::unsolvable("E can't be reached from S"))
+    }
+
+    fn solve_part_two(height_map: Self::Input<'_>, _: Self::Params) -> Result<String> {
+        height_map
+            .shortest_climb_from_lowest()
+            .map(|route| route.cost.to_string())
+            .ok_or_else(|| Error::unsolvable("E can't be reached from any square of elevation a"))
+    }
 }
 
-pub fn solve_part_two(input: String) -> String {
-    let mut graph = DiGraph::new();
-    let mut finish_node = NodeIndex::default();
-    let matrix: Vec<Vec<NodeIndex>> = input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|ch| {
-                    let node = graph.add_node(ch);
-                    if ch == 'E' {
-                        finish_node = node;
-                    }
-                    node
-                })
-                .collect()
+pub struct HeightMap {
+    heights: Grid<u32>,
+    start: IVec2,
+    finish: IVec2,
+}
+
+impl HeightMap {
+    pub fn from_input(input: &str) -> Result<Self> {
+        let squares = Grid::parse(input, "elevation", |ch| {
+            matches!(ch, 'a'..='z' | 'S' | 'E').then_some(ch)
+        })?;
+        let find = |target| {
+            squares
+                .iter()
+                .find(|&(_, &ch)| ch == target)
+                .map(|(pos, _)| pos)
+        };
+        let (Some(start), Some(finish)) = (find('S'), find('E')) else {
+            return Err(Error::parse(
+                input,
+                input,
+                "the map needs both an S and an E",
+            ));
+        };
+
+        Ok(Self {
+            heights: squares.map(|&ch| char_to_height(ch)),
+            start,
+            finish,
         })
-        .collect();
+    }
 
-    let h = matrix.len();
-    let w = matrix[0].len();
-    for (x, y) in (0..w).cartesian_product(0..h) {
-        let node = matrix[y][x];
-        let ch = graph[node];
+    pub fn start(&self) -> IVec2 {
+        self.start
+    }
 
-        let mut adjacent_indeces = Vec::new();
-        if x > 0 {
-            adjacent_indeces.push((x - 1, y));
-        }
-        if y > 0 {
-            adjacent_indeces.push((x, y - 1));
-        }
-        if x < w - 1 {
-            adjacent_indeces.push((x + 1, y));
-        }
-        if y < h - 1 {
-            adjacent_indeces.push((x, y + 1));
-        }
+    pub fn finish(&self) -> IVec2 {
+        self.finish
+    }
+
+    pub fn heights(&self) -> &Grid<u32> {
+        &self.heights
+    }
+
+    /// The shortest route from S to E, climbing at most one unit per step.
+    pub fn shortest_climb(&self) -> Option<Route<IVec2, usize>> {
+        bfs(
+            [self.start],
+            |&pos| self.climbable_from(pos),
+            |&pos| pos == self.finish,
+        )
+    }
+
+    /// The shortest route to E from any square of elevation a, found by searching back from E.
+    pub fn shortest_climb_from_lowest(&self) -> Option<Route<IVec2, usize>> {
+        let mut route = bfs(
+            [self.finish],
+            |&pos| self.descendable_from(pos),
+            |&pos| self.heights[pos] == 1,
+        )?;
+        route.nodes.reverse();
+        Some(route)
+    }
 
-        for (adj_x, adj_y) in adjacent_indeces {
-            let adjacent_node = matrix[adj_y][adj_x];
-            let adjacent_ch = graph[adjacent_node];
-            if !graph.contains_edge(node, adjacent_node)
-                && char_to_height(ch) + 1 >= char_to_height(adjacent_ch)
-            {
-                graph.add_edge(node, adjacent_node, ());
+    /// The fewest steps from every square to E, or `None` where E can't be reached.
+    pub fn steps_to_finish(&self) -> Grid<Option<usize>> {
+        let steps = bfs_distances([self.finish], |&pos| self.descendable_from(pos));
+        Grid::from_fn(self.heights.width(), self.heights.height(), |pos| {
+            steps.get(&pos).copied()
+        })
+    }
+
+    /// The map as in the input, with the route drawn over it in arrows pointing to the next square.
+    pub fn draw_route(&self, route: &Route<IVec2, usize>) -> String {
+        let mut squares = self.heights.map(|&height| height_to_char(height));
+        squares[self.start] = 'S';
+        squares[self.finish] = 'E';
+        for step in route.nodes.windows(2) {
+            if let Some(direction) = Direction4::from_ivec2(step[1] - step[0]) {
+                squares[step[0]] = direction.arrow();
             }
         }
+        squares.to_string()
     }
 
-    graph
-        .node_indices()
-        .map(|node| (node, graph[node]))
-        .filter(|(_, ch)| char_to_height(*ch) == 1)
-        .filter_map(|(node, _)| {
-            let distances = dijkstra(&graph, node, Some(finish_node), |_| 1);
-            distances.get(&finish_node).copied()
-        })
-        .min()
-        .unwrap()
-        .to_string()
+    /// A heat map of the elevation, from dark green valleys to white peaks, with the route in
+    /// red. Every square is `scale` pixels wide.
+    pub fn heat_map(&self, route: Option<&Route<IVec2, usize>>, scale: usize) -> Grid<Rgb> {
+        const ELEVATION: [Rgb; 4] = [
+            Rgb(20, 60, 30),
+            Rgb(90, 150, 60),
+            Rgb(150, 110, 70),
+            Rgb::WHITE,
+        ];
+        let mut picture = self
+            .heights
+            .map(|&height| Rgb::gradient(&ELEVATION, (height - 1) as f32 / 25.0));
+        for &pos in route.map_or(&[][..], |route| &route.nodes) {
+            picture[pos] = Rgb::RED;
+        }
+        image::scale(&picture, scale)
+    }
+
+    /// Writes [`HeightMap::heat_map`] as a binary PPM image.
+    pub fn write_heat_map(
+        &self,
+        route: Option<&Route<IVec2, usize>>,
+        scale: usize,
+        out: impl Write,
+    ) -> io::Result<()> {
+        image::write_ppm(&self.heat_map(route, scale), out)
+    }
+
+    /// The squares that can be climbed to from `pos`, which is at most one unit higher.
+    fn climbable_from(&self, pos: IVec2) -> impl Iterator<Item = IVec2> + '_ {
+        let height = self.heights[pos];
+        self.heights
+            .neighbours4(pos)
+            .filter(move |&next| self.heights[next] <= height + 1)
+    }
+
+    /// The squares that `pos` can be climbed to from, which is the climbing rule reversed.
+    fn descendable_from(&self, pos: IVec2) -> impl Iterator<Item = IVec2> + '_ {
+        let height = self.heights[pos];
+        self.heights
+            .neighbours4(pos)
+            .filter(move |&previous| height <= self.heights[previous] + 1)
+    }
 }
 
 fn char_to_height(ch: char) -> u32 {
@@ -130,3 +181,54 @@ fn char_to_height(ch: char) -> u32 {
         ch => alphabet_score(ch),
     }
 }
+
+fn height_to_char(height: u32) -> char {
+    char::from_u32('a' as u32 + height - 1).unwrap_or('?')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi";
+
+    #[test]
+    fn test_routes() {
+        let height_map = HeightMap::from_input(INPUT).unwrap();
+        let route = height_map.shortest_climb().unwrap();
+        assert_eq!(route.cost, 31);
+        assert_eq!(route.nodes.len(), 32);
+        assert_eq!(
+            (*route.start(), *route.goal()),
+            (height_map.start(), height_map.finish())
+        );
+
+        let route = height_map.shortest_climb_from_lowest().unwrap();
+        assert_eq!(route.cost, 29);
+        assert_eq!(height_map.heights()[*route.start()], 1);
+        assert_eq!(*route.goal(), height_map.finish());
+
+        let steps = height_map.steps_to_finish();
+        assert_eq!(steps[height_map.start()], Some(31));
+        assert_eq!(steps[height_map.finish()], Some(0));
+    }
+
+    #[test]
+    fn test_draw_route() {
+        let height_map = HeightMap::from_input(INPUT).unwrap();
+        let route = height_map.shortest_climb().unwrap();
+        assert_eq!(
+            height_map.draw_route(&route),
+            ">>vv<<<<\nabvvv<<^\nacvv>E^^\nacv>>>^^\nab>>>>>^"
+        );
+
+        let heat_map = height_map.heat_map(Some(&route), 3);
+        assert_eq!((heat_map.width(), heat_map.height()), (24, 15));
+        assert_eq!(heat_map[IVec2::new(2, 2)], Rgb::RED);
+        assert_eq!(heat_map[IVec2::new(0, 14)], Rgb(20, 60, 30));
+    }
+}
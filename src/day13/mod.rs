@@ -2,46 +2,60 @@ use std::cmp::Ordering;
 
 use itertools::Itertools;
 
-pub fn solve_part_one(input: String) -> String {
-    let packets = parsing::input(&input);
-
-    packets
-        .into_iter()
-        .enumerate()
-        .filter(|(_, (left, right))| left.cmp(right) == Ordering::Less)
-        .map(|(i, _)| i + 1)
-        .sum::<usize>()
-        .to_string()
-}
+use crate::{error::Result, solution::Solution};
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u32 = 13;
 
-pub fn solve_part_two(input: String) -> String {
-    let packets = parsing::input(&input);
+    type Input<'a> = Vec<(Packet, Packet)>;
+    type Params = ();
 
-    let mut packets = packets
-        .into_iter()
-        .flat_map(|(left, right)| [left, right])
-        .collect_vec();
+    const PARAMS: Self::Params = ();
+    const EXAMPLE_PARAMS: Self::Params = ();
 
-    let divider_packets = [
-        Packet::List(vec![Packet::List(vec![Packet::Number(6)])]),
-        Packet::List(vec![Packet::List(vec![Packet::Number(2)])]),
-    ];
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        parsing::input(input)
+    }
+
+    fn solve_part_one(packets: Self::Input<'_>, _: Self::Params) -> Result<String> {
+        Ok(packets
+            .into_iter()
+            .enumerate()
+            .filter(|(_, (left, right))| left.cmp(right) == Ordering::Less)
+            .map(|(i, _)| i + 1)
+            .sum::<usize>()
+            .to_string())
+    }
 
-    packets.extend(divider_packets.clone());
+    fn solve_part_two(packets: Self::Input<'_>, _: Self::Params) -> Result<String> {
+        let mut packets = packets
+            .into_iter()
+            .flat_map(|(left, right)| [left, right])
+            .collect_vec();
 
-    packets.sort_unstable();
+        let divider_packets = [
+            Packet::List(vec![Packet::List(vec![Packet::Number(6)])]),
+            Packet::List(vec![Packet::List(vec![Packet::Number(2)])]),
+        ];
 
-    packets
-        .into_iter()
-        .enumerate()
-        .filter(|(_, packet)| divider_packets.contains(packet))
-        .map(|(i, _)| i + 1)
-        .product::<usize>()
-        .to_string()
+        packets.extend(divider_packets.clone());
+
+        packets.sort_unstable();
+
+        Ok(packets
+            .into_iter()
+            .enumerate()
+            .filter(|(_, packet)| divider_packets.contains(packet))
+            .map(|(i, _)| i + 1)
+            .product::<usize>()
+            .to_string())
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-enum Packet {
+pub enum Packet {
     Number(u32),
     List(Vec<Packet>),
 }
@@ -88,6 +102,7 @@ impl Ord for Packet {
 
 mod parsing {
     use super::*;
+    use crate::common::parsing;
     use nom::{
         branch::alt,
         bytes::complete::tag,
@@ -97,14 +112,14 @@ mod parsing {
         IResult, Parser,
     };
 
-    pub(super) fn input(input: &str) -> Vec<(Packet, Packet)> {
-        let (_, packets) = separated_list1(
-            pair(line_ending, line_ending),
-            separated_pair(packet, line_ending, packet),
-        )(input)
-        .unwrap();
-
-        packets
+    pub(super) fn input(input: &str) -> Result<Vec<(Packet, Packet)>> {
+        parsing::complete(
+            input,
+            separated_list1(
+                pair(line_ending, line_ending),
+                separated_pair(packet, line_ending, packet),
+            ),
+        )
     }
 
     fn packet(input: &str) -> IResult<&str, Packet> {
@@ -122,6 +137,7 @@ mod parsing {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::solution::{InputKind, Part};
 
     const INPUT: &str = "[1,1,3,1,1]
 [1,1,5,1,1]
@@ -150,12 +166,18 @@ mod tests {
     #[test]
     fn test_part_one() {
         let expected = "13";
-        assert_eq!(solve_part_one(INPUT.to_string()), expected);
+        assert_eq!(
+            Day13::solve(INPUT, Part::One, InputKind::Example).unwrap(),
+            expected
+        );
     }
 
     #[test]
     fn test_part_two() {
         let expected = "140";
-        assert_eq!(solve_part_two(INPUT.to_string()), expected);
+        assert_eq!(
+            Day13::solve(INPUT, Part::Two, InputKind::Example).unwrap(),
+            expected
+        );
     }
 }
@@ -1,68 +1,116 @@
 use std::collections::HashSet;
-use std::fmt::Write;
 
 use glam::IVec2;
 
+use crate::{
+    common::{
+        grid::Grid,
+        math::{Direction8, IRect},
+    },
+    error::{Error, Result},
+    solution::Solution,
+};
+
 const SAND_ORIGIN: IVec2 = IVec2::new(500, 0);
-const MOVE_DIRECTIONS: [IVec2; 3] = [IVec2::Y, IVec2::new(-1, 1), IVec2::ONE];
+const MOVE_DIRECTIONS: [Direction8; 3] = [
+    Direction8::Down,
+    Direction8::DownLeft,
+    Direction8::DownRight,
+];
 
-pub fn solve_part_one(input: String) -> String {
-    let mut tiles = parsing::input(&input);
-    for i in 0.. {
-        let mut sand_pos = SAND_ORIGIN;
-        let mut moving = true;
-        while moving {
-            moving = false;
-            for move_dir in MOVE_DIRECTIONS {
-                let next_pos = sand_pos + move_dir;
-                if !tiles.contains(&next_pos) {
-                    sand_pos = next_pos;
-                    moving = true;
-                    break;
-                }
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u32 = 14;
+
+    type Input<'a> = Cave;
+    type Params = ();
+
+    const PARAMS: Self::Params = ();
+    const EXAMPLE_PARAMS: Self::Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let rocks = parsing::input(input)?;
+        Cave::new(&rocks)
+            .ok_or_else(|| Error::parse(input, input, "rocks must be below the sand source"))
+    }
+
+    fn solve_part_one(mut cave: Self::Input<'_>, _: Self::Params) -> Result<String> {
+        for i in 0.. {
+            let sand_pos = cave.drop_sand();
+            // Only sand that fell past every rock can end up on the floor.
+            if sand_pos.y == cave.floor_y - 1 {
+                return Ok(i.to_string());
             }
-            if !tiles
-                .iter()
-                .any(|tile_pos| tile_pos.x == sand_pos.x && tile_pos.y > sand_pos.y)
-            {
-                return i.to_string();
+        }
+
+        unreachable!()
+    }
+
+    fn solve_part_two(mut cave: Self::Input<'_>, _: Self::Params) -> Result<String> {
+        for i in 0.. {
+            if cave.drop_sand() == SAND_ORIGIN {
+                return Ok((i + 1).to_string());
             }
         }
-        tiles.insert(sand_pos);
+
+        unreachable!()
     }
+}
 
-    unreachable!()
+/// The slice of the cave that sand from [`SAND_ORIGIN`] can reach, down to the floor.
+pub struct Cave {
+    blocked: Grid<bool>,
+    /// The cave position of the grid's top left cell.
+    offset: IVec2,
+    floor_y: i32,
 }
 
-pub fn solve_part_two(input: String) -> String {
-    let mut tiles = parsing::input(&input);
-    let mut buf = String::new();
-    let floor = Floor::from_entries(&tiles);
-    for i in 0.. {
-        let mut sand_pos = SAND_ORIGIN;
-        let mut moving = true;
-        while moving {
-            moving = false;
-            for move_dir in MOVE_DIRECTIONS {
-                let next_pos = sand_pos + move_dir;
-                if !tiles.contains(&next_pos) && !floor.is_floor(next_pos) {
-                    sand_pos = next_pos;
-                    moving = true;
-                    break;
-                }
-            }
+impl Cave {
+    /// Returns `None` if there are no rocks, or rocks above the sand source.
+    fn new(rocks: &HashSet<IVec2>) -> Option<Self> {
+        let floor_y = rocks.iter().map(|pos| pos.y).max()? + 2;
+        if rocks.iter().any(|pos| pos.y < SAND_ORIGIN.y) {
+            return None;
         }
 
-        writeln!(&mut buf, "({},{})", sand_pos.x * 100, sand_pos.y * -100).unwrap();
+        // Sand piles up in a triangle, so it can't spread further sideways than the floor is deep.
+        let sand_reach = [
+            SAND_ORIGIN - IVec2::new(floor_y, 0),
+            SAND_ORIGIN + IVec2::new(floor_y, floor_y),
+        ];
+        let bounds = IRect::from_points(rocks.iter().copied().chain(sand_reach))?;
+        let offset = bounds.min();
+        let width = bounds.width() as usize;
+        let height = bounds.height() as usize;
+        let blocked = Grid::from_fn(width, height, |pos| {
+            let pos = pos + offset;
+            pos.y == floor_y || rocks.contains(&pos)
+        });
+        Some(Self {
+            blocked,
+            offset,
+            floor_y,
+        })
+    }
+
+    fn is_blocked(&self, pos: IVec2) -> bool {
+        self.blocked.get(pos - self.offset).copied().unwrap_or(true)
+    }
 
-        if sand_pos == SAND_ORIGIN {
-            std::fs::write("out.txt", buf).unwrap();
-            return (i + 1).to_string();
+    /// Lets one unit of sand fall from the source and returns where it comes to rest.
+    fn drop_sand(&mut self) -> IVec2 {
+        let mut sand_pos = SAND_ORIGIN;
+        while let Some(next_pos) = MOVE_DIRECTIONS
+            .into_iter()
+            .map(|move_dir| sand_pos + move_dir.to_ivec2())
+            .find(|&next_pos| !self.is_blocked(next_pos))
+        {
+            sand_pos = next_pos;
         }
-        tiles.insert(sand_pos);
+        self.blocked[sand_pos - self.offset] = true;
+        sand_pos
     }
-
-    unreachable!()
 }
 
 mod parsing {
@@ -78,10 +126,13 @@ mod parsing {
         IResult,
     };
 
-    pub(super) fn input(input: &str) -> HashSet<IVec2> {
+    use crate::{common::parsing, error::Result};
+
+    pub(super) fn input(input: &str) -> Result<HashSet<IVec2>> {
         let mut rocks = HashSet::new();
-        let (_, vertices) = vertices(input).unwrap();
+        let vertices = parsing::complete(input, vertices)?;
         for line in vertices {
+            rocks.insert(line[0]);
             for win in line.windows(2) {
                 let [from, to] = *win else { unreachable!() };
                 let line = to - from;
@@ -90,7 +141,7 @@ mod parsing {
                 }
             }
         }
-        rocks
+        Ok(rocks)
     }
 
     fn vertices(s: &str) -> IResult<&str, Vec<Vec<IVec2>>> {
@@ -109,22 +160,10 @@ mod parsing {
     }
 }
 
-struct Floor(i32);
-
-impl Floor {
-    pub fn from_entries(entries: &HashSet<IVec2>) -> Self {
-        let y = entries.iter().map(|pos| pos.y).max().unwrap() + 2;
-        Self(y)
-    }
-
-    pub fn is_floor(&self, pos: IVec2) -> bool {
-        pos.y >= self.0
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::solution::{InputKind, Part};
 
     const INPUT: &str = "498,4 -> 498,6 -> 496,6
 503,4 -> 502,4 -> 502,9 -> 494,9";
@@ -132,12 +171,18 @@ mod tests {
     #[test]
     fn test_part_one() {
         let expected = "24";
-        assert_eq!(solve_part_one(INPUT.to_string()), expected);
+        assert_eq!(
+            Day14::solve(INPUT, Part::One, InputKind::Example).unwrap(),
+            expected
+        );
     }
 
     #[test]
     fn test_part_two() {
         let expected = "93";
-        assert_eq!(solve_part_two(INPUT.to_string()), expected);
+        assert_eq!(
+            Day14::solve(INPUT, Part::Two, InputKind::Example).unwrap(),
+            expected
+        );
     }
 }
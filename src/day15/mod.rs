@@ -6,51 +6,319 @@
 // [X] determine positions where the distriss beacon cannot be present
 //   loop over all x positions in the specified y: determine if it is in the radius of one the sensors
 
-use std::collections::HashSet;
+use std::{fmt::Write, io};
 
 use glam::IVec2;
 use itertools::Itertools;
 
-use crate::common::math::{IRect, IVec2Ext};
-
-pub fn solve_part_one(input: String, row_to_check: i32) -> String {
-    let sensors = parsing::input(&input);
-    let beacons: HashSet<IVec2> = sensors.iter().map(|sensor| sensor.beacon).collect();
-    let bounds = calculate_bounds(&sensors);
-    (bounds.x1..=bounds.x2)
-        .map(|x| IVec2::new(x, row_to_check))
-        .filter(|&point| {
-            !beacons.contains(&point)
-                && sensors
-                    .iter()
-                    .any(|sensor| sensor.has_point_in_radius(point))
-        })
-        .count()
-        .to_string()
+use crate::{
+    common::{
+        grid::Grid,
+        image::{self, Rgb},
+        interval::{Interval, IntervalSet},
+        math::{IRect, IVec2Ext},
+    },
+    error::{Error, Result},
+    solution::Solution,
+};
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u32 = 15;
+
+    type Input<'a> = SensorField;
+    type Params = Params;
+
+    const PARAMS: Self::Params = Params {
+        row_to_check: 2_000_000,
+        size: 4_000_000,
+    };
+    const EXAMPLE_PARAMS: Self::Params = Params {
+        row_to_check: 10,
+        size: 20,
+    };
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        parsing::input(input)
+    }
+
+    fn solve_part_one(field: Self::Input<'_>, params: Self::Params) -> Result<String> {
+        let row_to_check = params.row_to_check;
+        let covered = field.coverage_of_row(row_to_check);
+        let beacons_in_row = field
+            .sensors()
+            .iter()
+            .map(|sensor| sensor.beacon)
+            .filter(|beacon| beacon.y == row_to_check && covered.contains(beacon.x.into()))
+            .unique()
+            .count() as u64;
+        Ok((covered.len() - beacons_in_row).to_string())
+    }
+
+    fn solve_part_two(field: Self::Input<'_>, params: Self::Params) -> Result<String> {
+        let size = params.size;
+        let distress_beacon_pos = field.first_uncovered(params.search_area()).ok_or_else(|| {
+            Error::unsolvable(format!(
+                "every position in the {size}x{size} search area is covered by a sensor"
+            ))
+        })?;
+
+        Ok(calculate_tuning_frequency(distress_beacon_pos).to_string())
+    }
 }
 
-pub fn solve_part_two(input: String, size: i32) -> String {
-    let sensors = parsing::input(&input);
-    let beacons: HashSet<IVec2> = sensors.iter().map(|sensor| sensor.beacon).collect();
-    let distress_beacon_pos = (0..size)
-        .cartesian_product(0..size)
-        .map(|(x, y)| IVec2::new(x, y))
-        .find(|&pos| {
-            if beacons.contains(&pos) {
-                return false;
-            }
+/// The sensors from the input, and the area they cover between them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SensorField {
+    sensors: Vec<Sensor>,
+}
+
+impl SensorField {
+    pub fn new(sensors: Vec<Sensor>) -> Self {
+        Self { sensors }
+    }
+
+    pub fn sensors(&self) -> &[Sensor] {
+        &self.sensors
+    }
 
-            sensors
+    pub fn sensors_covering(&self, pos: IVec2) -> impl Iterator<Item = &Sensor> + '_ {
+        self.sensors
+            .iter()
+            .filter(move |sensor| sensor.has_point_in_radius(pos))
+    }
+
+    pub fn is_covered(&self, pos: IVec2) -> bool {
+        self.sensors_covering(pos).next().is_some()
+    }
+
+    /// The x positions in row `y` that at least one sensor covers.
+    pub fn coverage_of_row(&self, y: i32) -> IntervalSet {
+        self.sensors
+            .iter()
+            .filter_map(|sensor| sensor.coverage_of_row(y))
+            .collect()
+    }
+
+    /// Every position in `area` that no sensor covers, row by row.
+    ///
+    /// Looks at every row of `area`, so use [`SensorField::first_uncovered`] for huge areas.
+    pub fn uncovered(&self, area: IRect) -> impl Iterator<Item = IVec2> + '_ {
+        (area.y1..=area.y2).flat_map(move |y| {
+            let row = Interval::new(area.x1.into(), area.x2.into())
+                .map(IntervalSet::from)
+                .unwrap_or_default();
+            let gaps = row.difference(&self.coverage_of_row(y));
+            gaps.intervals()
                 .iter()
-                .all(|sensor| !sensor.has_point_in_radius(pos))
+                .flat_map(|gap| gap.start()..=gap.end())
+                .map(move |x| IVec2::new(x as i32, y))
+                .collect::<Vec<_>>()
         })
-        .unwrap();
+    }
+
+    /// The first position in `area`, row by row, that no sensor covers.
+    ///
+    /// Merges the sensors' coverage of a row instead of checking every position. Every end of a
+    /// coverage interval moves by one from one row to the next, so the intervals that cover a row
+    /// with some overlap to spare are sure to cover the next few rows too, which are skipped.
+    pub fn first_uncovered(&self, area: IRect) -> Option<IVec2> {
+        let (left, last) = (i64::from(area.x1), i64::from(area.x2));
+        let mut coverage = Vec::with_capacity(self.sensors.len());
+        let mut y = area.y1;
+        while y <= area.y2 {
+            coverage.clear();
+            coverage.extend(
+                self.sensors
+                    .iter()
+                    .filter_map(|sensor| sensor.coverage_of_row(y)),
+            );
+            coverage.sort_unstable();
+
+            // `x` is the first position that isn't known to be covered yet.
+            let mut x = left;
+            let mut rows_to_skip = i64::MAX;
+            for interval in &coverage {
+                if interval.start() > x || x > last {
+                    break;
+                }
+                if interval.end() < x {
+                    continue;
+                }
+                // The left edge of the area stays put, but the end of the previous interval moves too.
+                let overlap = x - interval.start();
+                let overlap_rows = if x == left { overlap } else { overlap / 2 };
+                let length_rows = (interval.end() - interval.start()) / 2;
+                let right_edge_rows = if interval.end() >= last {
+                    interval.end() - last
+                } else {
+                    i64::MAX
+                };
+                rows_to_skip = rows_to_skip
+                    .min(overlap_rows)
+                    .min(length_rows)
+                    .min(right_edge_rows);
+                x = interval.end() + 1;
+            }
+            if x <= last {
+                return Some(IVec2::new(x as i32, y));
+            }
+            y = y
+                .saturating_add(rows_to_skip.min(i64::from(i32::MAX)) as i32)
+                .saturating_add(1);
+        }
+        None
+    }
+
+    /// The smallest rectangle around every sensor's diamond and every beacon.
+    pub fn bounds(&self) -> Option<IRect> {
+        let radius = |sensor: &Sensor| IVec2::splat(sensor.radius() as i32);
+        IRect::from_points(self.sensors.iter().flat_map(|sensor| {
+            [
+                sensor.center - radius(sensor),
+                sensor.center + radius(sensor),
+                sensor.beacon,
+            ]
+        }))
+    }
+
+    /// Draws `area` like the puzzle text does, with `S` for sensors, `B` for beacons, `#` for
+    /// covered positions and `.` for the rest.
+    pub fn draw(&self, area: IRect) -> String {
+        let squares = Grid::from_fn(area.width() as usize, area.height() as usize, |pos| {
+            self.square(pos + area.min())
+        });
+        squares.to_string()
+    }
 
-    calculate_tuning_frequency(distress_beacon_pos).to_string()
+    fn square(&self, pos: IVec2) -> char {
+        if self.sensors.iter().any(|sensor| sensor.center == pos) {
+            'S'
+        } else if self.sensors.iter().any(|sensor| sensor.beacon == pos) {
+            'B'
+        } else if self.is_covered(pos) {
+            '#'
+        } else {
+            '.'
+        }
+    }
+
+    /// A picture of `area` at most `max_size` pixels wide and high, shading every position by how
+    /// many sensors cover it, with the first uncovered position in `area` in red.
+    ///
+    /// Each pixel shows the position in the middle of the block it stands for, so a big area
+    /// shows the diamonds but not single positions, apart from the sensors, beacons and the
+    /// uncovered position, which are always drawn.
+    pub fn render(&self, area: IRect, max_size: usize) -> Grid<Rgb> {
+        const SHADES: [Rgb; 3] = [Rgb(20, 20, 40), Rgb(60, 110, 170), Rgb(200, 230, 255)];
+        const SENSOR: Rgb = Rgb(255, 200, 0);
+        const BEACON: Rgb = Rgb(40, 200, 80);
+
+        let side = area.width().max(area.height()) as usize;
+        let block = side.div_ceil(max_size.max(1)).max(1) as i32;
+        let width = (area.width() as usize).div_ceil(block as usize);
+        let height = (area.height() as usize).div_ceil(block as usize);
+        let most = self.sensors.len().max(1) as f32;
+        let mut picture = Grid::from_fn(width, height, |pixel| {
+            let pos = area.min() + pixel * block + IVec2::splat(block / 2);
+            let count = self.sensors_covering(pos).count();
+            if count == 0 {
+                Rgb::BLACK
+            } else {
+                Rgb::gradient(&SHADES, count as f32 / most)
+            }
+        });
+        let mut mark = |pos: IVec2, colour| {
+            if area.contains(pos) {
+                picture[(pos - area.min()) / block] = colour;
+            }
+        };
+        for sensor in &self.sensors {
+            mark(sensor.center, SENSOR);
+            mark(sensor.beacon, BEACON);
+        }
+        if let Some(pos) = self.first_uncovered(area) {
+            mark(pos, Rgb::RED);
+        }
+        picture
+    }
+
+    /// Writes [`SensorField::render`] as a binary PPM image.
+    pub fn write_ppm(&self, area: IRect, max_size: usize, out: impl io::Write) -> io::Result<()> {
+        image::write_ppm(&self.render(area, max_size), out)
+    }
+
+    /// An SVG of every sensor's diamond, the beacons, and the first uncovered position in
+    /// `search_area`, which is outlined.
+    pub fn to_svg(&self, search_area: IRect) -> String {
+        let bounds = self
+            .bounds()
+            .map_or(search_area, |bounds| bounds.union(search_area));
+        // Positions are the centres of unit squares, so the diamonds reach half a unit further.
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n",
+            f64::from(bounds.x1) - 0.5,
+            f64::from(bounds.y1) - 0.5,
+            bounds.width(),
+            bounds.height(),
+        );
+        let stroke = f64::from(bounds.width().max(bounds.height())) / 500.0;
+        let dot = stroke * 2.0;
+        let _ = writeln!(
+            svg,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"black\" stroke-width=\"{stroke}\"/>",
+            f64::from(search_area.x1) - 0.5,
+            f64::from(search_area.y1) - 0.5,
+            search_area.width(),
+            search_area.height(),
+        );
+        for sensor in &self.sensors {
+            let (x, y) = (f64::from(sensor.center.x), f64::from(sensor.center.y));
+            let r = f64::from(sensor.radius()) + 0.5;
+            let _ = writeln!(
+                svg,
+                "<polygon points=\"{x},{} {},{y} {x},{} {},{y}\" fill=\"steelblue\" fill-opacity=\"0.3\" stroke=\"steelblue\" stroke-width=\"{stroke}\"/>",
+                y - r,
+                x + r,
+                y + r,
+                x - r,
+            );
+        }
+        let mut circle = |pos: IVec2, colour: &str| {
+            let _ = writeln!(
+                svg,
+                "<circle cx=\"{}\" cy=\"{}\" r=\"{dot}\" fill=\"{colour}\"/>",
+                pos.x, pos.y
+            );
+        };
+        for sensor in &self.sensors {
+            circle(sensor.center, "orange");
+            circle(sensor.beacon, "green");
+        }
+        if let Some(pos) = self.first_uncovered(search_area) {
+            circle(pos, "red");
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Params {
+    pub row_to_check: i32,
+    pub size: i32,
+}
+
+impl Params {
+    /// Where part two looks for the distress beacon, from 0 to `size` in both directions.
+    pub fn search_area(self) -> IRect {
+        IRect::from_corners(IVec2::ZERO, IVec2::splat(self.size))
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Sensor {
+pub struct Sensor {
     pub center: IVec2,
     pub beacon: IVec2,
     radius: u32,
@@ -70,6 +338,13 @@ impl Sensor {
         self.radius
     }
 
+    /// The x positions in row `y` that are within the sensor's radius.
+    pub fn coverage_of_row(self, y: i32) -> Option<Interval> {
+        let half_width = i64::from(self.radius) - i64::from(self.center.y.abs_diff(y));
+        let x = i64::from(self.center.x);
+        Interval::new(x - half_width, x + half_width)
+    }
+
     // (x - center_x)² + (y - center_y)² < radius²
     pub fn has_point_in_radius(self, beacon_pos: IVec2) -> bool {
         self.center.manhattan_distance(beacon_pos) <= self.radius()
@@ -86,11 +361,10 @@ mod parsing {
     };
 
     use super::*;
+    use crate::common::parsing;
 
-    pub(super) fn input(s: &str) -> Vec<Sensor> {
-        let (_, sensors) = separated_list1(line_ending, sensor)(s).unwrap();
-
-        sensors
+    pub(super) fn input(s: &str) -> Result<SensorField> {
+        parsing::complete(s, separated_list1(line_ending, sensor)).map(SensorField::new)
     }
 
     fn sensor(s: &str) -> IResult<&str, Sensor> {
@@ -110,45 +384,14 @@ mod parsing {
     }
 }
 
-fn calculate_bounds(sensors: &[Sensor]) -> IRect {
-    let mut min_x = 0;
-    let mut max_x = 0;
-    let mut min_y = 0;
-    let mut max_y = 0;
-    for sensor in sensors {
-        let min_radius_x = sensor.center.x - (sensor.radius() as i32);
-        if min_radius_x < min_x {
-            min_x = min_radius_x;
-        }
-        let max_radius_x = sensor.center.x + (sensor.radius() as i32);
-        if max_radius_x > max_x {
-            max_x = max_radius_x;
-        }
-        let min_radius_y = sensor.center.y - (sensor.radius() as i32);
-        if min_radius_y < min_y {
-            min_y = min_radius_y;
-        }
-        let max_radius_y = sensor.center.y + (sensor.radius() as i32);
-        if max_radius_y > max_y {
-            max_y = max_radius_y;
-        }
-    }
-
-    IRect {
-        x1: min_x,
-        x2: max_x,
-        y1: min_y,
-        y2: max_y,
-    }
-}
-
-fn calculate_tuning_frequency(pos: IVec2) -> i32 {
-    pos.x * 4_000_000 + pos.y
+fn calculate_tuning_frequency(pos: IVec2) -> i64 {
+    i64::from(pos.x) * 4_000_000 + i64::from(pos.y)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::solution::{InputKind, Part};
 
     const INPUT: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
 Sensor at x=9, y=16: closest beacon is at x=10, y=16
@@ -167,15 +410,75 @@ Sensor at x=20, y=1: closest beacon is at x=15, y=3";
 
     #[test]
     fn test_part_one() {
-        let row_to_check = 10;
         let expected = "26";
-        assert_eq!(solve_part_one(INPUT.to_string(), row_to_check), expected);
+        assert_eq!(
+            Day15::solve(INPUT, Part::One, InputKind::Example).unwrap(),
+            expected
+        );
     }
 
     #[test]
     fn test_part_two() {
-        let size = 20;
         let expected = "56000011";
-        assert_eq!(solve_part_two(INPUT.to_string(), size), expected);
+        assert_eq!(
+            Day15::solve(INPUT, Part::Two, InputKind::Example).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_tuning_frequency() {
+        let pos = IVec2::new(3_999_999, 3_999_999);
+        assert_eq!(calculate_tuning_frequency(pos), 15_999_999_999_999);
+    }
+
+    #[test]
+    fn test_sensor_field() {
+        let field = parsing::input(INPUT).unwrap();
+        let search_area = IRect::from_corners(IVec2::ZERO, IVec2::splat(20));
+        let expected = "\
+##S##################
+####################S
+#############S#######
+##############SB#####
+#####################
+#####################
+#####################
+########S#######S####
+#####################
+#####################
+##B##################
+S#############.######
+#####################
+#####################
+############S#######S
+#####################
+#########SB##########
+##############S######
+##S##################
+#####################
+##########S######S###";
+        assert_eq!(field.draw(search_area), expected);
+
+        let covering = field.sensors_covering(IVec2::new(14, 10));
+        let centers = covering.map(|sensor| sensor.center).collect::<Vec<_>>();
+        assert_eq!(centers, [IVec2::new(8, 7), IVec2::new(16, 7)]);
+        assert_eq!(
+            field.coverage_of_row(11).intervals(),
+            [
+                Interval::new(-3, 13).unwrap(),
+                Interval::new(15, 25).unwrap()
+            ]
+        );
+        let uncovered = field.uncovered(search_area).collect::<Vec<_>>();
+        assert_eq!(uncovered, [IVec2::new(14, 11)]);
+        assert_eq!(field.first_uncovered(search_area), Some(IVec2::new(14, 11)));
+
+        let picture = field.render(search_area, 7);
+        assert_eq!((picture.width(), picture.height()), (7, 7));
+        assert_eq!(picture[IVec2::new(4, 3)], Rgb::RED);
+        assert!(field
+            .to_svg(search_area)
+            .contains("<circle cx=\"14\" cy=\"11\""));
     }
 }
@@ -1,19 +1,65 @@
-pub fn solve_part_one(input: String) -> String {
-    input
-        .lines()
-        .map(Round::from_part_one_input)
-        .map(|round| round.get_score())
-        .sum::<i32>()
-        .to_string()
-}
+use itertools::Itertools;
+
+use crate::{
+    error::{Error, Result},
+    solution::Solution,
+};
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u32 = 2;
+
+    type Input<'a> = Vec<(Shape, Response)>;
+    type Params = ();
+
+    const PARAMS: Self::Params = ();
+    const EXAMPLE_PARAMS: Self::Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        input
+            .lines()
+            .map(|line| {
+                let (opponent_symbol, response_symbol) = line
+                    .split_ascii_whitespace()
+                    .collect_tuple()
+                    .ok_or_else(|| Error::parse(input, line, "expected two symbols"))?;
+                let opponent_shape = Shape::from_symbol(opponent_symbol).ok_or_else(|| {
+                    Error::parse(
+                        input,
+                        opponent_symbol,
+                        format!("incorrect symbol: {opponent_symbol}"),
+                    )
+                })?;
+                let response = Response::from_symbol(response_symbol).ok_or_else(|| {
+                    Error::parse(
+                        input,
+                        response_symbol,
+                        format!("incorrect symbol: {response_symbol}"),
+                    )
+                })?;
+                Ok((opponent_shape, response))
+            })
+            .collect()
+    }
+
+    fn solve_part_one(rounds: Self::Input<'_>, _: Self::Params) -> Result<String> {
+        Ok(rounds
+            .into_iter()
+            .map(Round::from_part_one_input)
+            .map(|round| round.get_score())
+            .sum::<i32>()
+            .to_string())
+    }
 
-pub fn solve_part_two(input: String) -> String {
-    input
-        .lines()
-        .map(Round::from_part_two_input)
-        .map(|round| round.get_score())
-        .sum::<i32>()
-        .to_string()
+    fn solve_part_two(rounds: Self::Input<'_>, _: Self::Params) -> Result<String> {
+        Ok(rounds
+            .into_iter()
+            .map(Round::from_part_two_input)
+            .map(|round| round.get_score())
+            .sum::<i32>()
+            .to_string())
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -23,16 +69,15 @@ struct Round {
 }
 
 impl Round {
-    pub fn from_part_one_input(input: &str) -> Self {
+    pub fn from_part_one_input((opponent_shape, response): (Shape, Response)) -> Self {
         Self {
-            my_shape: Shape::from_symbol(&input[2..3]),
-            opponent_shape: Shape::from_symbol(&input[0..1]),
+            my_shape: response.to_shape(),
+            opponent_shape,
         }
     }
 
-    pub fn from_part_two_input(input: &str) -> Self {
-        let opponent_shape = Shape::from_symbol(&input[0..1]);
-        let outcome = MatchOutcome::from_symbol(&input[2..3]);
+    pub fn from_part_two_input((opponent_shape, response): (Shape, Response)) -> Self {
+        let outcome = response.to_outcome();
         let my_shape = Shape::from_outcome(outcome, opponent_shape);
         Self {
             my_shape,
@@ -48,19 +93,19 @@ impl Round {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
-enum Shape {
+pub enum Shape {
     Rock = 1,
     Paper,
     Scissors,
 }
 
 impl Shape {
-    pub fn from_symbol(symbol: &str) -> Self {
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
         match symbol {
-            "A" | "X" => Self::Rock,
-            "B" | "Y" => Self::Paper,
-            "C" | "Z" => Self::Scissors,
-            _ => panic!("Incorrect symbol: {symbol}"),
+            "A" => Some(Self::Rock),
+            "B" => Some(Self::Paper),
+            "C" => Some(Self::Scissors),
+            _ => None,
         }
     }
 
@@ -100,7 +145,7 @@ impl Shape {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
-enum MatchOutcome {
+pub enum MatchOutcome {
     Lose = 0,
     Draw = 3,
     Win = 6,
@@ -110,13 +155,39 @@ impl MatchOutcome {
     pub fn get_score(self) -> i32 {
         self as i32
     }
+}
+
+/// The second column of the strategy guide, which means something different in each part.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Response {
+    X,
+    Y,
+    Z,
+}
 
-    pub fn from_symbol(symbol: &str) -> Self {
+impl Response {
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
         match symbol {
-            "X" => Self::Lose,
-            "Y" => Self::Draw,
-            "Z" => Self::Win,
-            _ => panic!("Incorrect symbol"),
+            "X" => Some(Self::X),
+            "Y" => Some(Self::Y),
+            "Z" => Some(Self::Z),
+            _ => None,
+        }
+    }
+
+    pub fn to_shape(self) -> Shape {
+        match self {
+            Response::X => Shape::Rock,
+            Response::Y => Shape::Paper,
+            Response::Z => Shape::Scissors,
+        }
+    }
+
+    pub fn to_outcome(self) -> MatchOutcome {
+        match self {
+            Response::X => MatchOutcome::Lose,
+            Response::Y => MatchOutcome::Draw,
+            Response::Z => MatchOutcome::Win,
         }
     }
 }
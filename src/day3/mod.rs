@@ -1,42 +1,78 @@
 use std::collections::HashSet;
 
-use crate::common::string::alphabet_score;
-
-pub fn solve_part_one(input: String) -> String {
-    input
-        .lines()
-        .map(|rucksack| {
-            let middle_index = rucksack.len() / 2;
-            let (first_compartment, second_compartment) = rucksack.split_at(middle_index);
-            let first_compartment_items: HashSet<Item> = first_compartment.chars().collect();
-            let second_compartment_items: HashSet<Item> = second_compartment.chars().collect();
-            *first_compartment_items
-                .intersection(&second_compartment_items)
-                .next()
-                .unwrap()
-        })
-        .map(alphabet_score)
-        .sum::<u32>()
-        .to_string()
-}
+use crate::{
+    common::string::alphabet_score,
+    error::{Error, Result},
+    solution::Solution,
+};
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u32 = 3;
+
+    type Input<'a> = Vec<&'a str>;
+    type Params = ();
+
+    const PARAMS: Self::Params = ();
+    const EXAMPLE_PARAMS: Self::Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        input
+            .lines()
+            .map(|rucksack| {
+                if !rucksack.chars().all(|item| item.is_ascii_alphabetic()) {
+                    return Err(Error::parse(input, rucksack, "items must be ASCII letters"));
+                }
+                Ok(rucksack)
+            })
+            .collect()
+    }
+
+    fn solve_part_one(rucksacks: Self::Input<'_>, _: Self::Params) -> Result<String> {
+        rucksacks
+            .into_iter()
+            .map(|rucksack| {
+                let middle_index = rucksack.len() / 2;
+                let (first_compartment, second_compartment) = rucksack.split_at(middle_index);
+                let first_compartment_items: HashSet<Item> = first_compartment.chars().collect();
+                let second_compartment_items: HashSet<Item> = second_compartment.chars().collect();
+                first_compartment_items
+                    .intersection(&second_compartment_items)
+                    .next()
+                    .copied()
+                    .ok_or_else(|| {
+                        Error::unsolvable(format!("no item in both compartments of {rucksack}"))
+                    })
+            })
+            .map(|item| item.map(alphabet_score))
+            .sum::<Result<u32>>()
+            .map(|sum| sum.to_string())
+    }
 
-pub fn solve_part_two(input: String) -> String {
-    let lines = input.lines().collect::<Vec<_>>();
-    lines
-        .chunks_exact(3)
-        .map(|group| {
-            group
-                .iter()
-                .map(|rucksack| rucksack.chars().collect::<HashSet<_>>())
-                .reduce(|acc, val| acc.intersection(&val).copied().collect::<HashSet<_>>())
-                .unwrap()
-                .into_iter()
-                .next()
-                .unwrap()
-        })
-        .map(alphabet_score)
-        .sum::<u32>()
-        .to_string()
+    fn solve_part_two(rucksacks: Self::Input<'_>, _: Self::Params) -> Result<String> {
+        if rucksacks.len() % 3 != 0 {
+            return Err(Error::unsolvable(format!(
+                "{} rucksacks can't be split into groups of three",
+                rucksacks.len()
+            )));
+        }
+        rucksacks
+            .chunks_exact(3)
+            .map(|group| {
+                group
+                    .iter()
+                    .map(|rucksack| rucksack.chars().collect::<HashSet<_>>())
+                    .reduce(|acc, val| acc.intersection(&val).copied().collect::<HashSet<_>>())
+                    .and_then(|common_items| common_items.into_iter().next())
+                    .ok_or_else(|| {
+                        Error::unsolvable(format!("no badge shared by group {}", group.join(", ")))
+                    })
+            })
+            .map(|item| item.map(alphabet_score))
+            .sum::<Result<u32>>()
+            .map(|sum| sum.to_string())
+    }
 }
 
 type Item = char;
@@ -1,40 +1,62 @@
-use std::ops::RangeInclusive;
-
-pub fn solve_part_one(input: String) -> String {
-    input
-        .lines()
-        .map(parse_pair)
-        .filter(|(range1, range2)| {
-            range_fully_contains(range1, range2) || range_fully_contains(range2, range1)
-        })
-        .count()
-        .to_string()
-}
+use itertools::Itertools;
 
-pub fn solve_part_two(input: String) -> String {
-    input
-        .lines()
-        .map(parse_pair)
-        .filter(|(range1, range2)| ranges_overlap(range1, range2))
-        .count()
-        .to_string()
-}
+use crate::{
+    common::interval::Interval,
+    error::{Error, Result},
+    solution::Solution,
+};
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u32 = 4;
+
+    type Input<'a> = Vec<(Interval, Interval)>;
+    type Params = ();
+
+    const PARAMS: Self::Params = ();
+    const EXAMPLE_PARAMS: Self::Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        input.lines().map(|pair| parse_pair(input, pair)).collect()
+    }
+
+    fn solve_part_one(pairs: Self::Input<'_>, _: Self::Params) -> Result<String> {
+        Ok(pairs
+            .into_iter()
+            .filter(|(range1, range2)| {
+                range1.contains_interval(*range2) || range2.contains_interval(*range1)
+            })
+            .count()
+            .to_string())
+    }
 
-fn parse_pair(pair: &str) -> (RangeInclusive<i32>, RangeInclusive<i32>) {
-    let mut ranges = pair.split(',').map(|range| {
-        let mut ids = range.split('-').map(|id| id.parse::<i32>().unwrap());
-        ids.next().unwrap()..=ids.next().unwrap()
-    });
-    (ranges.next().unwrap(), ranges.next().unwrap())
+    fn solve_part_two(pairs: Self::Input<'_>, _: Self::Params) -> Result<String> {
+        Ok(pairs
+            .into_iter()
+            .filter(|(range1, range2)| range1.overlaps(*range2))
+            .count()
+            .to_string())
+    }
 }
 
-fn range_fully_contains(range1: &RangeInclusive<i32>, range2: &RangeInclusive<i32>) -> bool {
-    range1.start() <= range2.start() && range1.end() >= range2.end()
+fn parse_pair(input: &str, pair: &str) -> Result<(Interval, Interval)> {
+    let (range1, range2) = pair
+        .split(',')
+        .collect_tuple()
+        .ok_or_else(|| Error::parse(input, pair, "expected two comma-separated ranges"))?;
+    Ok((parse_range(input, range1)?, parse_range(input, range2)?))
 }
 
-fn ranges_overlap(range1: &RangeInclusive<i32>, range2: &RangeInclusive<i32>) -> bool {
-    range1.contains(range2.start())
-        || range1.contains(range2.end())
-        || range2.contains(range1.start())
-        || range2.contains(range1.end())
+fn parse_range(input: &str, range: &str) -> Result<Interval> {
+    let (start, end) = range
+        .split('-')
+        .collect_tuple()
+        .ok_or_else(|| Error::parse(input, range, "expected a range like 2-4"))?;
+    let parse_id = |id: &str| {
+        id.parse::<i64>()
+            .map_err(|err| Error::parse(input, id, format!("{err}: {id:?}")))
+    };
+    Interval::new(parse_id(start)?, parse_id(end)?)
+        .ok_or_else(|| Error::parse(input, range, "a range can't end before it starts"))
 }
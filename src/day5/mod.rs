@@ -2,90 +2,160 @@ use std::collections::VecDeque;
 
 use itertools::Itertools;
 
-use crate::common::string::ascii_char_at;
-
-pub fn solve_part_one(input: String) -> String {
-    let (mut crates, instructions) = parse_input(&input);
-    for instruction in instructions {
-        for _ in 0..instruction.quantity {
-            let to_add = crates[instruction.from].pop().unwrap();
-            crates[instruction.to].push(to_add);
-        }
-    }
+use crate::{
+    common::string::ascii_char_at,
+    error::{Error, Result},
+    solution::Solution,
+};
 
-    get_final_output(crates)
-}
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u32 = 5;
+
+    type Input<'a> = (Vec<Vec<Crate>>, Vec<Instruction>);
+    type Params = ();
+
+    const PARAMS: Self::Params = ();
+    const EXAMPLE_PARAMS: Self::Params = ();
 
-pub fn solve_part_two(input: String) -> String {
-    let (mut crates, instructions) = parse_input(&input);
-    for instruction in instructions {
-        let mut buf = VecDeque::new();
-        for _ in 0..instruction.quantity {
-            let to_add = crates[instruction.from].pop().unwrap();
-            buf.push_front(to_add);
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn solve_part_one(
+        (mut crates, instructions): Self::Input<'_>,
+        _: Self::Params,
+    ) -> Result<String> {
+        for instruction in instructions {
+            for _ in 0..instruction.quantity {
+                let to_add = pop_crate(&mut crates, instruction.from)?;
+                push_crate(&mut crates, instruction.to, to_add)?;
+            }
         }
-        crates[instruction.to].extend(buf);
+
+        get_final_output(crates)
     }
 
-    get_final_output(crates)
+    fn solve_part_two(
+        (mut crates, instructions): Self::Input<'_>,
+        _: Self::Params,
+    ) -> Result<String> {
+        for instruction in instructions {
+            let mut buf = VecDeque::new();
+            for _ in 0..instruction.quantity {
+                let to_add = pop_crate(&mut crates, instruction.from)?;
+                buf.push_front(to_add);
+            }
+            for to_add in buf {
+                push_crate(&mut crates, instruction.to, to_add)?;
+            }
+        }
+
+        get_final_output(crates)
+    }
 }
 
 type Crate = char;
 
 #[derive(Debug)]
-struct Instruction {
+pub struct Instruction {
     pub quantity: usize,
     pub from: usize,
     pub to: usize,
 }
 
-fn parse_input(input: &str) -> (Vec<Vec<Crate>>, Vec<Instruction>) {
-    let mut split = input.split("\n\n");
-    let crates_area = split.next().unwrap();
-    let instructions_area = split.next().unwrap();
-    (
-        parse_crates_area(crates_area),
-        parse_instructions_area(instructions_area),
-    )
+fn parse_input(input: &str) -> Result<(Vec<Vec<Crate>>, Vec<Instruction>)> {
+    let (crates_area, instructions_area) = input.split_once("\n\n").ok_or_else(|| {
+        Error::parse(
+            input,
+            input,
+            "expected the crates and the instructions to be separated by a blank line",
+        )
+    })?;
+    Ok((
+        parse_crates_area(input, crates_area)?,
+        parse_instructions_area(input, instructions_area)?,
+    ))
 }
 
-fn parse_crates_area(crates_area: &str) -> Vec<Vec<Crate>> {
+fn parse_crates_area(input: &str, crates_area: &str) -> Result<Vec<Vec<Crate>>> {
+    if !crates_area.is_ascii() {
+        return Err(Error::parse(input, crates_area, "crates must be ASCII"));
+    }
     let mut lines = crates_area.lines().collect_vec();
     lines.pop(); // remove the unnecessary numbers section at the bottom
-    let strings_len = lines[0].len();
-    (1..strings_len)
+    let strings_len = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+    Ok((1..strings_len)
         .step_by(4)
         .map(|i| {
             lines
                 .iter()
                 .rev()
-                .map(|stack| ascii_char_at(stack, i))
+                .map(|stack| ascii_char_at(stack, i).unwrap_or(' '))
                 .take_while(|&ch| ch != ' ')
                 .collect_vec()
         })
-        .collect_vec()
+        .collect_vec())
 }
 
-fn parse_instructions_area(instructions_area: &str) -> Vec<Instruction> {
+fn parse_instructions_area(input: &str, instructions_area: &str) -> Result<Vec<Instruction>> {
     instructions_area
         .lines()
         .map(|line| {
-            let mut sections = line.split_ascii_whitespace();
-            let quantity = sections.nth(1).unwrap();
-            let from = sections.nth(1).unwrap();
-            let to = sections.nth(1).unwrap();
-            Instruction {
-                quantity: quantity.parse().unwrap(),
-                from: from.parse::<usize>().unwrap() - 1,
-                to: to.parse::<usize>().unwrap() - 1,
-            }
+            let Some(("move", quantity, "from", from, "to", to)) =
+                line.split_ascii_whitespace().collect_tuple()
+            else {
+                return Err(Error::parse(
+                    input,
+                    line,
+                    "expected an instruction like `move 1 from 2 to 3`",
+                ));
+            };
+            let parse_number = |number: &str| {
+                number
+                    .parse::<usize>()
+                    .map_err(|err| Error::parse(input, number, format!("{err}: {number:?}")))
+            };
+            let parse_stack = |stack: &str| {
+                parse_number(stack)?
+                    .checked_sub(1)
+                    .ok_or_else(|| Error::parse(input, stack, "stacks are numbered from 1"))
+            };
+            Ok(Instruction {
+                quantity: parse_number(quantity)?,
+                from: parse_stack(from)?,
+                to: parse_stack(to)?,
+            })
         })
         .collect()
 }
 
-fn get_final_output(crates: Vec<Vec<Crate>>) -> String {
+fn pop_crate(crates: &mut [Vec<Crate>], from: usize) -> Result<Crate> {
+    crates
+        .get_mut(from)
+        .ok_or_else(|| Error::unsolvable(format!("there is no stack {}", from + 1)))?
+        .pop()
+        .ok_or_else(|| Error::unsolvable(format!("stack {} is empty", from + 1)))
+}
+
+fn push_crate(crates: &mut [Vec<Crate>], to: usize, to_add: Crate) -> Result<()> {
+    crates
+        .get_mut(to)
+        .ok_or_else(|| Error::unsolvable(format!("there is no stack {}", to + 1)))?
+        .push(to_add);
+    Ok(())
+}
+
+fn get_final_output(crates: Vec<Vec<Crate>>) -> Result<String> {
     crates
         .into_iter()
-        .map(|crate_| *crate_.last().unwrap())
+        .enumerate()
+        .map(|(i, crate_)| {
+            crate_
+                .last()
+                .copied()
+                .ok_or_else(|| Error::unsolvable(format!("stack {} ends up empty", i + 1)))
+        })
         .collect()
 }
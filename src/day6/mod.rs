@@ -1,25 +1,50 @@
-use itertools::Itertools;
+use crate::{
+    common::iterator::first_distinct_byte_window,
+    error::{Error, Result},
+    solution::Solution,
+};
 
-use crate::common::iterator::is_all_unique;
+pub struct Day6;
 
-pub fn solve_part_one(input: String) -> String {
-    const MARKER_SIZE: usize = 4;
-    find_marker_end_index(input, MARKER_SIZE).to_string()
-}
+impl Solution for Day6 {
+    const DAY: u32 = 6;
+
+    type Input<'a> = &'a str;
+    type Params = ();
+
+    const PARAMS: Self::Params = ();
+    const EXAMPLE_PARAMS: Self::Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let datastream = input.trim_end();
+        match datastream.char_indices().find(|(_, ch)| !ch.is_ascii()) {
+            Some((index, ch)) => Err(Error::parse(
+                input,
+                &datastream[index..],
+                format!("expected an ASCII character, found {ch:?}"),
+            )),
+            None => Ok(datastream),
+        }
+    }
+
+    fn solve_part_one(datastream: Self::Input<'_>, _: Self::Params) -> Result<String> {
+        const MARKER_SIZE: usize = 4;
+        find_marker_end_index(datastream, MARKER_SIZE).map(|index| index.to_string())
+    }
 
-pub fn solve_part_two(input: String) -> String {
-    const MARKER_SIZE: usize = 14;
-    find_marker_end_index(input, MARKER_SIZE).to_string()
+    fn solve_part_two(datastream: Self::Input<'_>, _: Self::Params) -> Result<String> {
+        const MARKER_SIZE: usize = 14;
+        find_marker_end_index(datastream, MARKER_SIZE).map(|index| index.to_string())
+    }
 }
 
-fn find_marker_end_index(input: String, marker_size: usize) -> usize {
-    let chars = input.chars().collect_vec();
-    let start_index_of_marker = chars
-        .windows(marker_size)
-        .enumerate()
-        .find(|(_, window)| is_all_unique(*window))
-        .unwrap()
-        .0;
+fn find_marker_end_index(datastream: &str, marker_size: usize) -> Result<usize> {
+    let start_index_of_marker = first_distinct_byte_window(datastream.as_bytes(), marker_size)
+        .ok_or_else(|| {
+            Error::unsolvable(format!(
+                "no run of {marker_size} distinct characters in the datastream"
+            ))
+        })?;
 
-    start_index_of_marker + marker_size
+    Ok(start_index_of_marker + marker_size)
 }
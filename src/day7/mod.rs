@@ -3,60 +3,94 @@ use std::{
     fmt::{self},
 };
 
-use indextree::{Arena, NodeEdge};
+use indextree::{Arena, NodeEdge, NodeId};
 use itertools::Itertools;
 
-pub fn solve_part_one(input: String) -> String {
-    const MAX_DIR_SIZE: FileSize = 100_000;
-    let commands: Vec<Command> = parse_input(&input);
-    let filesystem = FileSystem::from_commands(commands);
-    filesystem
-        .get_dir_sizes()
-        .into_iter()
-        .filter(|(_, size)| *size <= MAX_DIR_SIZE)
-        .map(|(_, size)| size)
-        .sum::<FileSize>()
-        .to_string()
-}
+use crate::{
+    error::{Error, Result},
+    solution::Solution,
+};
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u32 = 7;
+
+    type Input<'a> = FileSystem<'a>;
+    type Params = ();
+
+    const PARAMS: Self::Params = ();
+    const EXAMPLE_PARAMS: Self::Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        FileSystem::from_commands(parse_input(input)?)
+    }
 
-pub fn solve_part_two(input: String) -> String {
-    const MAX_SPACE: FileSize = 70_000_000;
-    const FREE_SPACE_REQUIRED: FileSize = 30_000_000;
-
-    let filesystem = FileSystem::from_commands(parse_input(&input));
-    let sizes = filesystem.get_dir_sizes();
-    let root_size = sizes.iter().find(|(name, _)| name == "/").unwrap().1;
-    let free_space = MAX_SPACE - root_size;
-    let space_to_free = FREE_SPACE_REQUIRED - free_space;
-
-    sizes
-        .into_iter()
-        .map(|(_, size)| size)
-        .filter(|size| *size >= space_to_free)
-        .min()
-        .unwrap()
-        .to_string()
+    fn solve_part_one(filesystem: Self::Input<'_>, _: Self::Params) -> Result<String> {
+        const MAX_DIR_SIZE: FileSize = 100_000;
+        Ok(filesystem
+            .get_dir_sizes()?
+            .into_iter()
+            .filter(|(_, size)| *size <= MAX_DIR_SIZE)
+            .map(|(_, size)| size)
+            .sum::<FileSize>()
+            .to_string())
+    }
+
+    fn solve_part_two(filesystem: Self::Input<'_>, _: Self::Params) -> Result<String> {
+        const MAX_SPACE: FileSize = 70_000_000;
+        const FREE_SPACE_REQUIRED: FileSize = 30_000_000;
+
+        let sizes = filesystem.get_dir_sizes()?;
+        let root_size = sizes
+            .iter()
+            .find(|(name, _)| name == "/")
+            .map(|(_, size)| *size)
+            .ok_or_else(|| Error::unsolvable("the filesystem has no root directory"))?;
+        let free_space = MAX_SPACE.checked_sub(root_size).ok_or_else(|| {
+            Error::unsolvable(format!(
+                "the filesystem takes up {root_size}, more than the disk's {MAX_SPACE}"
+            ))
+        })?;
+        let space_to_free = FREE_SPACE_REQUIRED.saturating_sub(free_space);
+
+        sizes
+            .into_iter()
+            .map(|(_, size)| size)
+            .filter(|size| *size >= space_to_free)
+            .min()
+            .map(|size| size.to_string())
+            .ok_or_else(|| Error::unsolvable("no directory is large enough to delete"))
+    }
 }
 
 #[derive(Default, PartialEq)]
-struct FileSystem<'a> {
+pub struct FileSystem<'a> {
     files: Arena<FsEntry<'a>>,
 }
 
 impl<'a> FileSystem<'a> {
-    pub fn from_commands(commands: Vec<Command<'a>>) -> Self {
+    fn from_commands(commands: Vec<Command<'a>>) -> Result<Self> {
         let mut files = Arena::new();
         let mut current_dir = files.new_node(FsEntry::Dir { name: "/" });
 
         for command in commands {
             match command {
                 Command::Cd(cd_type) => match cd_type {
-                    CdType::Up => current_dir = files[current_dir].parent().unwrap(),
+                    CdType::Up => {
+                        current_dir = files[current_dir]
+                            .parent()
+                            .ok_or_else(|| Error::unsolvable("can't `cd ..` out of /"))?
+                    }
                     CdType::Down(target) => {
                         current_dir = current_dir
                             .children(&files)
                             .find(|child| *files[*child].get() == FsEntry::Dir { name: target })
-                            .unwrap()
+                            .ok_or_else(|| {
+                                Error::unsolvable(format!(
+                                    "can't `cd` into {target}, it hasn't been listed"
+                                ))
+                            })?
                     }
                 },
                 Command::Ls(entries) => {
@@ -68,44 +102,40 @@ impl<'a> FileSystem<'a> {
             }
         }
 
-        Self { files }
+        Ok(Self { files })
     }
 
-    pub fn get_dir_sizes(&self) -> Vec<(String, FileSize)> {
-        let root = self.files.iter().next().unwrap();
-        let root_id = self.files.get_node_id(root).unwrap();
-        let mut sizes: HashMap<String, FileSize> = HashMap::new();
-        let mut current_dir = String::from("/");
+    /// The total size of every directory, including everything below it, with its name.
+    pub fn get_dir_sizes(&self) -> Result<Vec<(String, FileSize)>> {
+        let root_id = self
+            .files
+            .iter()
+            .next()
+            .and_then(|root| self.files.get_node_id(root))
+            .ok_or_else(|| Error::unsolvable("the filesystem has no root directory"))?;
+
+        // Sizes of the directories that are still being added up, by node.
+        let mut totals: HashMap<NodeId, FileSize> = HashMap::new();
+        let mut sizes = Vec::new();
         for edge in root_id.traverse(&self.files) {
-            match edge {
-                NodeEdge::Start(node_id) => match self.files[node_id].get() {
-                    FsEntry::Dir { name } => {
-                        let id = format!("{name}_{node_id}");
-                        sizes.insert(id.clone(), 0);
-                        current_dir = id;
-                    }
-                    FsEntry::File { size, .. } => *sizes.get_mut(&current_dir).unwrap() += size,
-                },
-                NodeEdge::End(node_id) => {
-                    let node = &self.files[node_id];
-                    if let FsEntry::Dir { name } = node.get() {
-                        if *name != "/" {
-                            let parent_id = node.parent().unwrap();
-                            let parent = &self.files[parent_id];
-                            let parent_name = parent.get().name();
-                            current_dir = format!("{parent_name}_{parent_id}");
-                            *sizes.get_mut(&current_dir).unwrap() +=
-                                sizes[&format!("{name}_{node_id}")];
-                        }
-                    }
+            let NodeEdge::End(node_id) = edge else {
+                continue;
+            };
+            let node = &self.files[node_id];
+            let size = match node.get() {
+                FsEntry::File { size, .. } => *size,
+                FsEntry::Dir { name } => {
+                    let size = totals.remove(&node_id).unwrap_or_default();
+                    sizes.push((name.to_string(), size));
+                    size
                 }
+            };
+            if let Some(parent_id) = node.parent() {
+                *totals.entry(parent_id).or_default() += size;
             }
         }
 
-        sizes
-            .into_iter()
-            .map(|(name, size)| (name.split('_').next().unwrap().to_string(), size))
-            .collect_vec()
+        Ok(sizes)
     }
 }
 
@@ -124,21 +154,17 @@ enum FsEntry<'a> {
 }
 
 impl<'a> FsEntry<'a> {
-    pub fn from_str(s: &'a str) -> Self {
-        if s.starts_with("dir") {
-            Self::Dir { name: &s[4..] }
+    pub fn from_str(input: &str, s: &'a str) -> Result<Self> {
+        if let Some(name) = s.strip_prefix("dir ") {
+            Ok(Self::Dir { name })
         } else {
-            let mut parts = s.split_ascii_whitespace();
-            let size = parts.next().unwrap().parse().unwrap();
-            let name = parts.next().unwrap();
-            Self::File { name, size }
-        }
-    }
-
-    pub fn name(&self) -> &'a str {
-        match self {
-            FsEntry::Dir { name } => name,
-            FsEntry::File { name, .. } => name,
+            let (size, name) = s.split_ascii_whitespace().collect_tuple().ok_or_else(|| {
+                Error::parse(input, s, "expected `dir <name>` or `<size> <name>`")
+            })?;
+            let size = size
+                .parse()
+                .map_err(|err| Error::parse(input, size, format!("{err}: {size:?}")))?;
+            Ok(Self::File { name, size })
         }
     }
 }
@@ -165,28 +191,49 @@ impl<'a> CdType<'a> {
     }
 }
 
-fn parse_input(input: &str) -> Vec<Command> {
+fn parse_input(input: &str) -> Result<Vec<Command<'_>>> {
     let mut commands = Vec::new();
 
-    let mut lines = input.lines().skip(1).peekable();
+    let mut lines = input.lines().peekable();
+    if lines.next() != Some("$ cd /") {
+        return Err(Error::parse(
+            input,
+            input,
+            "expected the session to start with `$ cd /`",
+        ));
+    }
     while let Some(line) = lines.next() {
-        if line.starts_with("$ cd") {
-            let cd_target = &line[5..];
+        if let Some(cd_target) = line.strip_prefix("$ cd ") {
             commands.push(Command::Cd(CdType::from_str(cd_target)))
-        } else if line.starts_with("$ ls") {
+        } else if line == "$ ls" {
             let mut entries = Vec::new();
-            #[allow(clippy::while_let_on_iterator)]
-            while let Some(entry) = lines.next() {
-                entries.push(FsEntry::from_str(entry));
-                if let Some(entry) = lines.peek() {
-                    if entry.starts_with('$') {
-                        break;
-                    }
-                }
+            while let Some(entry) = lines.next_if(|entry| !entry.starts_with('$')) {
+                entries.push(FsEntry::from_str(input, entry)?);
             }
             commands.push(Command::Ls(entries));
+        } else {
+            return Err(Error::parse(input, line, "expected a `cd` or `ls` command"));
         }
     }
 
-    commands
+    Ok(commands)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{InputKind, Part};
+
+    #[test]
+    fn test_malformed_sessions() {
+        for input in [
+            "$ cd /\n$ cd ..",
+            "$ cd /\n$ cd a",
+            "$ cd /\n$ ls\nabc a",
+            "$ ls",
+        ] {
+            let result = Day7::solve(input, Part::One, InputKind::Example);
+            assert!(result.is_err(), "{input:?} gave {result:?}");
+        }
+    }
 }
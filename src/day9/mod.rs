@@ -3,72 +3,86 @@ use std::collections::HashSet;
 use glam::{IVec2, Vec2};
 use itertools::Itertools;
 
-use crate::common::math::Vec2Ext;
+use crate::{
+    common::math::{Direction4, Vec2Ext},
+    error::{Error, Result},
+    solution::Solution,
+};
 
-pub fn solve_part_one(input: String) -> String {
-    let moves = parse_input(&input);
-    let mut head_position = Vec2::default();
-    let mut tail_position = Vec2::default();
-    let mut tail_positions: HashSet<IVec2> = HashSet::from([IVec2::ZERO]);
-    for mov in moves {
-        // move head
-        head_position += mov;
-        // check distance from head to tail
-        let distance = head_position.distance(tail_position);
-        // move tail
-        if distance >= 2.0 {
-            let direction = (head_position - tail_position)
-                .normalize_or_zero()
-                .to_direction();
-            tail_position += direction;
-            tail_positions.insert(tail_position.as_ivec2());
-        }
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u32 = 9;
+
+    type Input<'a> = Vec<Direction4>;
+    type Params = ();
+
+    const PARAMS: Self::Params = ();
+    const EXAMPLE_PARAMS: Self::Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        parse_input(input)
     }
-    tail_positions.len().to_string()
-}
 
-pub fn solve_part_two(input: String) -> String {
-    const NUM_KNOTS: usize = 10;
-    let moves = parse_input(&input);
-    let mut knot_positions = [Vec2::ZERO; NUM_KNOTS];
-    let mut tail_positions: HashSet<IVec2> = HashSet::from([IVec2::ZERO]);
-    for mov in moves {
-        // move head
-        knot_positions[0] += mov;
-        // check distance from first to second
-        for i in 0..NUM_KNOTS - 1 {
-            let distance = knot_positions[i].distance(knot_positions[i + 1]);
+    fn solve_part_one(moves: Self::Input<'_>, _: Self::Params) -> Result<String> {
+        let mut head_position = Vec2::default();
+        let mut tail_position = Vec2::default();
+        let mut tail_positions: HashSet<IVec2> = HashSet::from([IVec2::ZERO]);
+        for mov in moves {
+            // move head
+            head_position += mov.to_ivec2().as_vec2();
+            // check distance from head to tail
+            let distance = head_position.distance(tail_position);
             // move tail
             if distance >= 2.0 {
-                let direction = (knot_positions[i] - knot_positions[i + 1])
+                let direction = (head_position - tail_position)
                     .normalize_or_zero()
                     .to_direction();
-                knot_positions[i + 1] += direction;
+                tail_position += direction;
+                tail_positions.insert(tail_position.as_ivec2());
             }
         }
-        tail_positions.insert(knot_positions.last().unwrap().as_ivec2());
+        Ok(tail_positions.len().to_string())
     }
-    tail_positions.len().to_string()
-}
 
-fn parse_input(input: &str) -> Vec<Vec2> {
-    input
-        .lines()
-        .flat_map(|line| {
-            let (direction, count) = line.split_ascii_whitespace().collect_tuple().unwrap();
-            let direction = direction_from_letter(direction);
-            let count: usize = count.parse().unwrap();
-            std::iter::repeat(direction).take(count)
-        })
-        .collect_vec()
+    fn solve_part_two(moves: Self::Input<'_>, _: Self::Params) -> Result<String> {
+        const NUM_KNOTS: usize = 10;
+        let mut knot_positions = [Vec2::ZERO; NUM_KNOTS];
+        let mut tail_positions: HashSet<IVec2> = HashSet::from([IVec2::ZERO]);
+        for mov in moves {
+            // move head
+            knot_positions[0] += mov.to_ivec2().as_vec2();
+            // check distance from first to second
+            for i in 0..NUM_KNOTS - 1 {
+                let distance = knot_positions[i].distance(knot_positions[i + 1]);
+                // move tail
+                if distance >= 2.0 {
+                    let direction = (knot_positions[i] - knot_positions[i + 1])
+                        .normalize_or_zero()
+                        .to_direction();
+                    knot_positions[i + 1] += direction;
+                }
+            }
+            tail_positions.insert(knot_positions[NUM_KNOTS - 1].as_ivec2());
+        }
+        Ok(tail_positions.len().to_string())
+    }
 }
 
-fn direction_from_letter(letter: &str) -> Vec2 {
-    match letter {
-        "L" => Vec2::NEG_X,
-        "U" => Vec2::Y,
-        "R" => Vec2::X,
-        "D" => Vec2::NEG_Y,
-        _ => panic!("Invalid direction letter: {letter}"),
+fn parse_input(input: &str) -> Result<Vec<Direction4>> {
+    let mut moves = Vec::new();
+    for line in input.lines() {
+        let (direction, count) = line
+            .split_ascii_whitespace()
+            .collect_tuple()
+            .ok_or_else(|| Error::parse(input, line, "expected a direction and a step count"))?;
+        let direction = direction
+            .parse::<Direction4>()
+            .map_err(|err| Error::parse(input, direction, err))?;
+        let count: usize = count
+            .parse()
+            .map_err(|err| Error::parse(input, count, format!("{err}: {count:?}")))?;
+        moves.extend(std::iter::repeat_n(direction, count));
     }
+    Ok(moves)
 }
@@ -0,0 +1,97 @@
+use std::{fmt, io};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    /// The puzzle input doesn't have the expected shape. `line` and `column` are 1-based.
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// The input parsed fine, but has no answer under the puzzle's rules.
+    Unsolvable(String),
+    /// A request to the Advent of Code website failed.
+    Http(String),
+}
+
+impl Error {
+    /// Creates a parse error located at `fragment`, which must be a subslice of `input`.
+    pub fn parse(input: &str, fragment: &str, message: impl Into<String>) -> Self {
+        let offset = (fragment.as_ptr() as usize)
+            .saturating_sub(input.as_ptr() as usize)
+            .min(input.len());
+        let consumed = input.get(..offset).unwrap_or(input);
+        let line = consumed.matches('\n').count() + 1;
+        let column = consumed.chars().rev().take_while(|&ch| ch != '\n').count() + 1;
+        Self::Parse {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    pub fn unsolvable(message: impl Into<String>) -> Self {
+        Self::Unsolvable(message.into())
+    }
+
+    pub fn http(message: impl Into<String>) -> Self {
+        Self::Http(message.into())
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "I/O error: {err}"),
+            Error::Parse {
+                line,
+                column,
+                message,
+            } => write!(f, "parse error at line {line}, column {column}: {message}"),
+            Error::Unsolvable(message) => write!(f, "no solution: {message}"),
+            Error::Http(message) => write!(f, "HTTP error: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_location() {
+        let input = "first\nsecond line\nthird";
+        let fragment = &input[13..];
+        let Error::Parse { line, column, .. } = Error::parse(input, fragment, "oops") else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((line, column), (2, 8));
+    }
+
+    #[test]
+    fn test_parse_location_at_start() {
+        let input = "abc";
+        let Error::Parse { line, column, .. } = Error::parse(input, input, "oops") else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((line, column), (1, 1));
+    }
+}
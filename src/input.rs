@@ -0,0 +1,109 @@
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::PathBuf,
+};
+
+use crate::{error::Result, solution::InputKind};
+
+/// A directory with one subdirectory per day, each holding `input.txt`, `test_input.txt` and the
+/// expected answers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputsDir(pub PathBuf);
+
+impl InputsDir {
+    /// Environment variable that overrides the default inputs directory.
+    pub const ENV_VAR: &'static str = "AOC_INPUTS_DIR";
+
+    /// The directory in `AOC_INPUTS_DIR` if it's set, otherwise `./src`.
+    pub fn from_env() -> Self {
+        env::var_os(Self::ENV_VAR)
+            .filter(|dir| !dir.is_empty())
+            .map_or_else(Self::default, |dir| Self(dir.into()))
+    }
+
+    pub fn day_dir(&self, day_number: u32) -> PathBuf {
+        self.0.join(format!("day{day_number}"))
+    }
+
+    pub fn input_path(&self, day_number: u32, kind: InputKind) -> PathBuf {
+        let filename = match kind {
+            InputKind::Real => "input.txt",
+            InputKind::Example => "test_input.txt",
+        };
+        self.day_dir(day_number).join(filename)
+    }
+
+    pub fn read(&self, day_number: u32, kind: InputKind) -> Result<String> {
+        read_file(self.input_path(day_number, kind))
+    }
+}
+
+impl Default for InputsDir {
+    fn default() -> Self {
+        Self(PathBuf::from("./src"))
+    }
+}
+
+/// Where to read a day's puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// The day's own `input.txt` or `test_input.txt` in an inputs directory.
+    Day(InputsDir, InputKind),
+    Path(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Inputs from a path or stdin are treated as real inputs.
+    pub fn kind(&self) -> InputKind {
+        match self {
+            InputSource::Day(_, kind) => *kind,
+            InputSource::Path(_) | InputSource::Stdin => InputKind::Real,
+        }
+    }
+
+    pub fn read(&self, day_number: u32) -> Result<String> {
+        match self {
+            InputSource::Day(inputs, kind) => inputs.read(day_number, *kind),
+            InputSource::Path(path) => read_file(path.clone()),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+fn read_file(path: PathBuf) -> Result<String> {
+    fs::read_to_string(&path)
+        .map_err(|err| io::Error::new(err.kind(), format!("{}: {err}", path.display())).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_path() {
+        let inputs = InputsDir(PathBuf::from("/tmp/aoc"));
+        assert_eq!(
+            inputs.input_path(3, InputKind::Real),
+            PathBuf::from("/tmp/aoc/day3/input.txt")
+        );
+        assert_eq!(
+            inputs.input_path(12, InputKind::Example),
+            PathBuf::from("/tmp/aoc/day12/test_input.txt")
+        );
+    }
+
+    #[test]
+    fn test_default_layout() {
+        let source = InputSource::Day(InputsDir::default(), InputKind::Example);
+        assert_eq!(
+            source.read(1).unwrap(),
+            fs::read_to_string("./src/day1/test_input.txt").unwrap()
+        );
+    }
+}
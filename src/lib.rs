@@ -1,3 +1,6 @@
+pub mod answers;
+pub mod bench;
+pub mod client;
 mod common;
 pub mod day1;
 pub mod day10;
@@ -14,3 +17,10 @@ pub mod day6;
 pub mod day7;
 pub mod day8;
 pub mod day9;
+pub mod error;
+pub mod input;
+pub mod registry;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod submit;
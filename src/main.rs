@@ -1,57 +1,462 @@
-use std::{env, fs};
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::{ArgGroup, Args, Parser, Subcommand};
 
 use advent_of_code_2022::{
-    day1, day10, day11, day12, day13, day14, day15, day2, day3, day4, day5, day6, day7, day8, day9,
+    bench::{self, BenchOptions},
+    client::{self, Client},
+    day10::{Day10, Screen},
+    day12::HeightMap,
+    day15::Day15,
+    input::{InputSource, InputsDir},
+    registry::{self, Day, DAYS},
+    runner::{self, Table},
+    scaffold,
+    solution::{InputKind, Part, Solution},
+    submit::{self, Submission, Verdict},
 };
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    let day_number = args[1].parse::<u32>().unwrap();
-    let puzzle_number = args[2].parse::<u32>().unwrap();
-    let is_test = args.get(3) == Some(&String::from("test"));
-    let input = read_input_of_day(day_number, is_test);
-    let result: String = match (day_number, puzzle_number) {
-        (1, 1) => day1::solve_part_one(input),
-        (1, 2) => day1::solve_part_two(input),
-        (2, 1) => day2::solve_part_one(input),
-        (2, 2) => day2::solve_part_two(input),
-        (3, 1) => day3::solve_part_one(input),
-        (3, 2) => day3::solve_part_two(input),
-        (4, 1) => day4::solve_part_one(input),
-        (4, 2) => day4::solve_part_two(input),
-        (5, 1) => day5::solve_part_one(input),
-        (5, 2) => day5::solve_part_two(input),
-        (6, 1) => day6::solve_part_one(input),
-        (6, 2) => day6::solve_part_two(input),
-        (7, 1) => day7::solve_part_one(input),
-        (7, 2) => day7::solve_part_two(input),
-        (8, 1) => day8::solve_part_one(input),
-        (8, 2) => day8::solve_part_two(input),
-        (9, 1) => day9::solve_part_one(input),
-        (9, 2) => day9::solve_part_two(input),
-        (10, 1) => day10::solve_part_one(input),
-        (10, 2) => day10::solve_part_two(input),
-        (11, 1) => day11::solve_part_one(input),
-        (11, 2) => day11::solve_part_two(input),
-        (12, 1) => day12::solve_part_one(input),
-        (12, 2) => day12::solve_part_two(input),
-        (13, 1) => day13::solve_part_one(input),
-        (13, 2) => day13::solve_part_two(input),
-        (14, 1) => day14::solve_part_one(input),
-        (14, 2) => day14::solve_part_two(input),
-        (15, 1) => day15::solve_part_one(input, 2_000_000),
-        (15, 2) => day15::solve_part_two(input, 4_000_000),
-        _ => panic!("Day {day_number} or puzzle {puzzle_number} not found"),
-    };
-    println!("Result:\n{result}");
-}
-
-fn read_input_of_day(day_number: u32, is_test: bool) -> String {
-    let filename = if is_test {
-        "test_input.txt"
+/// Solutions to Advent of Code 2022.
+#[derive(Debug, Parser)]
+#[command(version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Look for `dayN/input.txt` and `dayN/test_input.txt` in DIR. Defaults to
+    /// `$AOC_INPUTS_DIR`, or `./src` if that isn't set.
+    #[arg(long, global = true, value_name = "DIR")]
+    inputs_dir: Option<PathBuf>,
+}
+
+impl Cli {
+    fn inputs_dir(&self) -> InputsDir {
+        self.inputs_dir
+            .clone()
+            .map_or_else(InputsDir::from_env, InputsDir)
+    }
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Solve one day and print the answers.
+    Run {
+        /// The day to solve.
+        #[arg(value_parser = parse_day)]
+        day: &'static Day,
+        /// Only solve this part.
+        #[arg(long, value_parser = parse_part)]
+        part: Option<Part>,
+        /// Answer day 10 part two with the screen itself, instead of the letters read from it.
+        #[arg(long)]
+        raw: bool,
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Solve every day and check the answers against each day's answers.toml.
+    All {
+        /// Use the examples from the puzzle text instead of the real inputs.
+        #[arg(long)]
+        example: bool,
+    },
+    /// Time parsing and solving over many runs.
+    Bench {
+        /// The day to benchmark. Benchmarks every day if left out.
+        #[arg(value_parser = parse_day)]
+        day: Option<&'static Day>,
+        /// Only benchmark this part.
+        #[arg(long, value_parser = parse_part)]
+        part: Option<Part>,
+        /// Use the examples from the puzzle text instead of the real inputs.
+        #[arg(long)]
+        example: bool,
+        /// Runs to do and throw away before measuring.
+        #[arg(long, default_value_t = BenchOptions::default().warmup)]
+        warmup: u32,
+        /// Runs to measure.
+        #[arg(
+            long,
+            default_value_t = BenchOptions::default().repetitions,
+            value_parser = clap::value_parser!(u32).range(1..),
+        )]
+        repetitions: u32,
+    },
+    /// Download real inputs into the inputs directory, skipping days that already have one.
+    Fetch {
+        /// The day to download. Downloads every day if left out.
+        #[arg(value_parser = parse_day)]
+        day: Option<&'static Day>,
+    },
+    /// Solve a part on the real input and submit the answer.
+    ///
+    /// Answers already known to be wrong, or out of the bounds set by earlier too high and too
+    /// low answers, aren't sent. Every attempt is recorded in the day's submissions.toml.
+    Submit {
+        #[arg(value_parser = parse_day)]
+        day: &'static Day,
+        #[arg(value_parser = parse_part)]
+        part: Part,
+        /// Submit this answer instead of solving the part.
+        #[arg(long)]
+        answer: Option<String>,
+    },
+    /// Draw day 12's shortest climb over the height map, as arrows and optionally as an image.
+    Climb {
+        /// Draw the shortest climb from any square of elevation a, instead of from S.
+        #[arg(long)]
+        from_lowest: bool,
+        /// Also write a heat map of the elevation with the route on top to PATH, as a PPM image.
+        #[arg(long, value_name = "PATH")]
+        ppm: Option<PathBuf>,
+        /// Pixels per square in the heat map.
+        #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..))]
+        scale: u32,
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Draw day 15's sensors over the part two search area, with the distress beacon in red.
+    #[command(group(ArgGroup::new("images").required(true).multiple(true).args(["ppm", "svg"])))]
+    Sensors {
+        /// Write a picture shaded by how many sensors cover each position to PATH, as a PPM image.
+        #[arg(long, value_name = "PATH")]
+        ppm: Option<PathBuf>,
+        /// Write the sensors' diamonds to PATH, as an SVG image.
+        #[arg(long, value_name = "PATH")]
+        svg: Option<PathBuf>,
+        /// The largest width and height of the PPM image, in pixels.
+        #[arg(long, default_value_t = 1000, value_parser = clap::value_parser!(u32).range(1..))]
+        max_size: u32,
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Create and register the module for a new day. Run from the crate root.
+    New {
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+    },
+}
+
+#[derive(Debug, Args)]
+#[group(multiple = false)]
+struct InputArgs {
+    /// Read the input from PATH, or from stdin if PATH is `-`.
+    #[arg(long, value_name = "PATH")]
+    input: Option<PathBuf>,
+    /// Use the example from the puzzle text instead of the real input.
+    #[arg(long)]
+    example: bool,
+}
+
+impl InputArgs {
+    fn source(&self, inputs: InputsDir) -> InputSource {
+        match &self.input {
+            Some(path) if path.as_os_str() == "-" => InputSource::Stdin,
+            Some(path) => InputSource::Path(path.clone()),
+            None => InputSource::Day(inputs, input_kind(self.example)),
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let inputs = cli.inputs_dir();
+    match cli.command {
+        Command::Run {
+            day,
+            part,
+            raw,
+            input,
+        } => run(day, part, raw, input.source(inputs)),
+        Command::All { example } => run_all(&inputs, input_kind(example)),
+        Command::Bench {
+            day,
+            part,
+            example,
+            warmup,
+            repetitions,
+        } => run_bench(
+            day,
+            part,
+            &inputs,
+            input_kind(example),
+            BenchOptions {
+                warmup,
+                repetitions,
+            },
+        ),
+        Command::Fetch { day } => fetch(day, &inputs),
+        Command::Submit { day, part, answer } => submit(day, part, answer, &inputs),
+        Command::Climb {
+            from_lowest,
+            ppm,
+            scale,
+            input,
+        } => climb(input.source(inputs), from_lowest, ppm.as_deref(), scale),
+        Command::Sensors {
+            ppm,
+            svg,
+            max_size,
+            input,
+        } => sensors(
+            input.source(inputs),
+            ppm.as_deref(),
+            svg.as_deref(),
+            max_size,
+        ),
+        Command::New { day } => new_day(day),
+    }
+}
+
+fn run(day: &Day, part: Option<Part>, raw: bool, source: InputSource) -> ExitCode {
+    if raw && day.number != Day10::DAY {
+        eprintln!("Day {}: --raw only applies to day 10", day.number);
+        return ExitCode::FAILURE;
+    }
+    let input = match source.read(day.number) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Day {}: {err}", day.number);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut failed = false;
+    for part in part.map_or(Part::ALL.to_vec(), |part| vec![part]) {
+        let answer = if raw && part == Part::Two {
+            Day10::parse(&input)
+                .and_then(|instructions| Day10::solve_part_two(instructions, Screen::Picture))
+        } else {
+            day.solve(&input, part, source.kind())
+        };
+        match answer {
+            Ok(answer) => println!("Part {part}:\n{answer}"),
+            Err(err) => {
+                eprintln!("Day {} part {part}: {err}", day.number);
+                failed = true;
+            }
+        }
+    }
+    exit_code(failed)
+}
+
+fn run_all(inputs: &InputsDir, kind: InputKind) -> ExitCode {
+    let runs = runner::run_all(inputs, kind);
+    let table = Table(&runs);
+    println!("{table}");
+    exit_code(!table.all_passed())
+}
+
+fn run_bench(
+    day: Option<&Day>,
+    part: Option<Part>,
+    inputs: &InputsDir,
+    kind: InputKind,
+    options: BenchOptions,
+) -> ExitCode {
+    let days = day.map_or(DAYS.iter().collect(), |day| vec![day]);
+    let parts = part.map_or(Part::ALL.to_vec(), |part| vec![part]);
+
+    let mut reports = Vec::new();
+    let mut failed = false;
+    for day in days {
+        for &part in &parts {
+            match bench::bench(day, part, inputs, kind, options) {
+                Ok(report) => reports.push(report),
+                Err(err) => {
+                    eprintln!("Day {} part {part}: {err}", day.number);
+                    failed = true;
+                }
+            }
+        }
+    }
+
+    println!(
+        "{} runs after {} warmup runs",
+        options.repetitions, options.warmup
+    );
+    print!("{}", bench::Table(&reports));
+    exit_code(failed)
+}
+
+fn fetch(day: Option<&Day>, inputs: &InputsDir) -> ExitCode {
+    let client = Client::from_env();
+    let days = day.map_or(DAYS.iter().collect(), |day| vec![day]);
+
+    let mut failed = false;
+    for day in days {
+        let path = inputs.input_path(day.number, InputKind::Real);
+        match client::cached_input(&client, inputs, day.number) {
+            Ok(_) => println!("Day {}: {}", day.number, path.display()),
+            Err(err) => {
+                eprintln!("Day {}: {err}", day.number);
+                failed = true;
+            }
+        }
+    }
+    exit_code(failed)
+}
+
+fn submit(day: &Day, part: Part, answer: Option<String>, inputs: &InputsDir) -> ExitCode {
+    let answer = match answer {
+        Some(answer) => Ok(answer),
+        None => inputs
+            .read(day.number, InputKind::Real)
+            .and_then(|input| day.solve(&input, part, InputKind::Real)),
+    };
+    let submission = answer.and_then(|answer| {
+        println!("Submitting {answer}");
+        submit::submit(&Client::from_env(), inputs, day.number, part, &answer)
+    });
+
+    match submission {
+        Ok(Submission::Sent(outcome)) => {
+            println!("The answer is {}", outcome.verdict);
+            if let Some(wait) = outcome.wait {
+                println!("Wait {}s before submitting again", wait.as_secs());
+            }
+            exit_code(outcome.verdict != Verdict::Right)
+        }
+        Ok(Submission::Refused(refusal)) => {
+            eprintln!("Not submitted: {refusal}");
+            ExitCode::FAILURE
+        }
+        Err(err) => {
+            eprintln!("Day {} part {part}: {err}", day.number);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn climb(source: InputSource, from_lowest: bool, ppm: Option<&Path>, scale: u32) -> ExitCode {
+    const DAY: u32 = 12;
+    let height_map = match source
+        .read(DAY)
+        .and_then(|input| HeightMap::from_input(&input))
+    {
+        Ok(height_map) => height_map,
+        Err(err) => {
+            eprintln!("Day {DAY}: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let route = if from_lowest {
+        height_map.shortest_climb_from_lowest()
     } else {
-        "input.txt"
+        height_map.shortest_climb()
+    };
+    let Some(route) = route else {
+        eprintln!("Day {DAY}: E can't be reached");
+        return ExitCode::FAILURE;
+    };
+    println!("{}", height_map.draw_route(&route));
+
+    if let Some(path) = ppm {
+        let written = File::create(path).and_then(|file| {
+            let mut out = BufWriter::new(file);
+            height_map.write_heat_map(Some(&route), scale as usize, &mut out)?;
+            out.flush()
+        });
+        match written {
+            Ok(()) => println!("Wrote {}", path.display()),
+            Err(err) => {
+                eprintln!("Day {DAY}: {}: {err}", path.display());
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn sensors(source: InputSource, ppm: Option<&Path>, svg: Option<&Path>, max_size: u32) -> ExitCode {
+    let field = match source
+        .read(Day15::DAY)
+        .and_then(|input| Day15::parse(&input))
+    {
+        Ok(field) => field,
+        Err(err) => {
+            eprintln!("Day {}: {err}", Day15::DAY);
+            return ExitCode::FAILURE;
+        }
+    };
+    let params = match source.kind() {
+        InputKind::Real => Day15::PARAMS,
+        InputKind::Example => Day15::EXAMPLE_PARAMS,
+    };
+    let area = params.search_area();
+
+    let mut failed = false;
+    let mut write = |path: &Path, contents: &dyn Fn(&mut BufWriter<File>) -> io::Result<()>| {
+        let written = File::create(path).and_then(|file| {
+            let mut out = BufWriter::new(file);
+            contents(&mut out)?;
+            out.flush()
+        });
+        match written {
+            Ok(()) => println!("Wrote {}", path.display()),
+            Err(err) => {
+                eprintln!("Day {}: {}: {err}", Day15::DAY, path.display());
+                failed = true;
+            }
+        }
     };
-    let input_path = format!("./src/day{day_number}/{filename}");
-    fs::read_to_string(input_path).unwrap()
+    if let Some(path) = ppm {
+        write(path, &|out| field.write_ppm(area, max_size as usize, out));
+    }
+    if let Some(path) = svg {
+        write(path, &|out| out.write_all(field.to_svg(area).as_bytes()));
+    }
+    exit_code(failed)
+}
+
+fn new_day(day_number: u32) -> ExitCode {
+    match scaffold::new_day(Path::new("./src"), day_number) {
+        Ok(paths) => {
+            for path in paths {
+                println!("Wrote {}", path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("Day {day_number}: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn parse_day(arg: &str) -> Result<&'static Day, String> {
+    let day_number = arg
+        .parse::<u32>()
+        .map_err(|_| format!("`{arg}` is not a day number"))?;
+    registry::get(day_number).ok_or_else(|| {
+        let first = DAYS.first().map_or(0, |day| day.number);
+        let last = DAYS.last().map_or(0, |day| day.number);
+        format!("day {day_number} isn't solved yet, pick one of {first}-{last}")
+    })
+}
+
+fn parse_part(arg: &str) -> Result<Part, String> {
+    arg.parse::<u32>()
+        .ok()
+        .and_then(Part::from_number)
+        .ok_or_else(|| format!("`{arg}` is not a part, pick 1 or 2"))
+}
+
+fn input_kind(example: bool) -> InputKind {
+    if example {
+        InputKind::Example
+    } else {
+        InputKind::Real
+    }
+}
+
+fn exit_code(failed: bool) -> ExitCode {
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
 }
@@ -0,0 +1,91 @@
+use crate::{
+    day1::Day1,
+    day10::Day10,
+    day11::Day11,
+    day12::Day12,
+    day13::Day13,
+    day14::Day14,
+    day15::Day15,
+    day2::Day2,
+    day3::Day3,
+    day4::Day4,
+    day5::Day5,
+    day6::Day6,
+    day7::Day7,
+    day8::Day8,
+    day9::Day9,
+    error::Result,
+    solution::{InputKind, Part, Solution, Timings},
+};
+
+/// Every implemented day, in calendar order.
+pub static DAYS: [Day; 15] = [
+    Day::of::<Day1>(),
+    Day::of::<Day2>(),
+    Day::of::<Day3>(),
+    Day::of::<Day4>(),
+    Day::of::<Day5>(),
+    Day::of::<Day6>(),
+    Day::of::<Day7>(),
+    Day::of::<Day8>(),
+    Day::of::<Day9>(),
+    Day::of::<Day10>(),
+    Day::of::<Day11>(),
+    Day::of::<Day12>(),
+    Day::of::<Day13>(),
+    Day::of::<Day14>(),
+    Day::of::<Day15>(),
+];
+
+pub fn get(number: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+/// A type-erased [`Solution`], so that days can be looked up and iterated at runtime.
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub number: u32,
+    solve_timed: fn(&str, Part, InputKind) -> Result<(String, Timings)>,
+}
+
+impl Day {
+    pub const fn of<S: Solution>() -> Self {
+        Self {
+            number: S::DAY,
+            solve_timed: S::solve_timed,
+        }
+    }
+
+    pub fn solve(&self, input: &str, part: Part, kind: InputKind) -> Result<String> {
+        self.solve_timed(input, part, kind)
+            .map(|(answer, _)| answer)
+    }
+
+    pub fn solve_timed(
+        &self,
+        input: &str,
+        part: Part,
+        kind: InputKind,
+    ) -> Result<(String, Timings)> {
+        (self.solve_timed)(input, part, kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days_are_in_order() {
+        for pair in DAYS.windows(2) {
+            assert!(pair[0].number < pair[1].number);
+        }
+    }
+
+    #[test]
+    fn test_get() {
+        assert_eq!(get(15).map(|day| day.number), Some(15));
+        assert!(get(0).is_none());
+        assert!(get(26).is_none());
+    }
+}
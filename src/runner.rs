@@ -0,0 +1,280 @@
+use std::{
+    fmt, io,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    answers::Answers,
+    error::{Error, Result},
+    input::InputsDir,
+    registry::{Day, DAYS},
+    solution::{InputKind, Part},
+};
+
+/// The outcome of running one part of one day.
+#[derive(Debug)]
+pub struct Run {
+    pub day: u32,
+    pub part: Part,
+    pub answer: Result<String>,
+    /// Time spent parsing and solving, not reading the input.
+    pub elapsed: Duration,
+    pub status: Status,
+    /// Why the answer couldn't be checked, if the known answers couldn't be loaded.
+    pub warning: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail {
+        expected: String,
+    },
+    /// There is no stored answer to compare against.
+    Unchecked,
+    /// The input file for this day doesn't exist.
+    NoInput,
+    Error,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail { expected } => write!(f, "FAIL (expected {})", first_line(expected)),
+            Status::Unchecked => write!(f, "?"),
+            Status::NoInput => write!(f, "no input"),
+            Status::Error => write!(f, "ERROR"),
+        }
+    }
+}
+
+pub fn run(day: &Day, part: Part, inputs: &InputsDir, kind: InputKind) -> Run {
+    let expected = Answers::load(inputs, day.number)
+        .map(|answers| answers.expected(part, kind).map(str::to_string));
+    let input = inputs.read(day.number, kind);
+    let input_missing =
+        matches!(&input, Err(Error::Io(err)) if err.kind() == io::ErrorKind::NotFound);
+    let start = Instant::now();
+    let answer = input.and_then(|input| day.solve(&input, part, kind));
+    let elapsed = start.elapsed();
+
+    // A broken answers.toml shouldn't cost the answer, so it's only a warning.
+    let (expected, warning) = match expected {
+        Ok(expected) => (expected, None),
+        Err(err) => (None, Some(format!("can't check the answer: {err}"))),
+    };
+    let (answer, status) = match (answer, expected) {
+        (Err(err), _) if input_missing => (Err(err), Status::NoInput),
+        (Err(err), _) => (Err(err), Status::Error),
+        (Ok(answer), None) => (Ok(answer), Status::Unchecked),
+        (Ok(answer), Some(expected)) if answers_match(&answer, &expected) => {
+            (Ok(answer), Status::Pass)
+        }
+        (Ok(answer), Some(expected)) => (Ok(answer), Status::Fail { expected }),
+    };
+
+    Run {
+        day: day.number,
+        part,
+        answer,
+        elapsed,
+        status,
+        warning,
+    }
+}
+
+/// Runs both parts of every registered day, in order.
+pub fn run_all(inputs: &InputsDir, kind: InputKind) -> Vec<Run> {
+    DAYS.iter()
+        .flat_map(|day| Part::ALL.map(|part| run(day, part, inputs, kind)))
+        .collect()
+}
+
+/// Formats runs as a table with one row per part, followed by a summary line.
+pub struct Table<'a>(pub &'a [Run]);
+
+impl Table<'_> {
+    pub fn all_passed(&self) -> bool {
+        self.0
+            .iter()
+            .all(|run| !matches!(run.status, Status::Fail { .. } | Status::Error))
+    }
+}
+
+impl fmt::Display for Table<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let answers = self
+            .0
+            .iter()
+            .map(|run| match &run.answer {
+                Ok(answer) => first_line(answer),
+                Err(err) => err.to_string(),
+            })
+            .collect::<Vec<_>>();
+        let answer_width = answers
+            .iter()
+            .map(|answer| answer.chars().count())
+            .chain(["Answer".len()])
+            .max()
+            .unwrap_or_default();
+
+        writeln!(
+            f,
+            "{:>3}  {:>4}  {:<answer_width$}  {:>10}  Status",
+            "Day", "Part", "Answer", "Time"
+        )?;
+        for (run, answer) in self.0.iter().zip(&answers) {
+            writeln!(
+                f,
+                "{:>3}  {:>4}  {:<answer_width$}  {:>10}  {}",
+                run.day,
+                run.part,
+                answer,
+                format!("{:.2?}", run.elapsed),
+                run.status
+            )?;
+        }
+
+        let mut warned = Vec::new();
+        for run in self.0 {
+            if let Some(warning) = &run.warning {
+                if !warned.contains(&(run.day, warning)) {
+                    writeln!(f, "warning: day {}: {warning}", run.day)?;
+                    warned.push((run.day, warning));
+                }
+            }
+        }
+
+        let count = |predicate: fn(&Status) -> bool| {
+            self.0.iter().filter(|run| predicate(&run.status)).count()
+        };
+        let total_time: Duration = self.0.iter().map(|run| run.elapsed).sum();
+        write!(
+            f,
+            "{} passed, {} failed, {} errors, {} unchecked, {} without input in {:.2?}",
+            count(|status| *status == Status::Pass),
+            count(|status| matches!(status, Status::Fail { .. })),
+            count(|status| *status == Status::Error),
+            count(|status| *status == Status::Unchecked),
+            count(|status| *status == Status::NoInput),
+            total_time
+        )
+    }
+}
+
+/// Trailing whitespace is ignored, so multi-line answers can be stored as TOML multi-line strings.
+fn answers_match(answer: &str, expected: &str) -> bool {
+    answer.trim_end() == expected.trim_end()
+}
+
+/// Multi-line answers (like day 10's screen) are shortened to their first line.
+fn first_line(answer: &str) -> String {
+    let mut lines = answer.lines();
+    let first = lines.next().unwrap_or_default();
+    if lines.next().is_some() {
+        format!("{first}…")
+    } else {
+        first.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use super::*;
+    use crate::{client::tests::temp_dir, scaffold, solution::Solution};
+
+    /// A part that is scaffolded but not solved yet, so it has neither an answer nor an expected
+    /// one. These are skipped instead of failing the suite.
+    fn is_unsolved(run: &Run, inputs: &InputsDir) -> bool {
+        let answers = Answers::load(inputs, run.day).unwrap();
+        run.answer.is_err() && answers.expected(run.part, InputKind::Example).is_none()
+    }
+
+    /// Every example run passes, apart from unsolved parts.
+    fn check_example_answers(runs: &[Run], inputs: &InputsDir) {
+        let table = Table(runs);
+        for run in runs {
+            assert!(
+                run.status == Status::Pass || is_unsolved(run, inputs),
+                "day {} part {} has no example answer, or doesn't match it\n{table}",
+                run.day,
+                run.part
+            );
+        }
+    }
+
+    #[test]
+    fn test_example_answers() {
+        let inputs = InputsDir::default();
+        check_example_answers(&run_all(&inputs, InputKind::Example), &inputs);
+    }
+
+    #[test]
+    fn test_scaffolded_day_is_skipped() {
+        /// Behaves like the solution `scaffold::module` writes for a new day.
+        struct Unsolved;
+
+        impl Solution for Unsolved {
+            // Past the end of the calendar, so it never clashes with a real day.
+            const DAY: u32 = 26;
+
+            type Input<'a> = &'a str;
+            type Params = ();
+
+            const PARAMS: Self::Params = ();
+            const EXAMPLE_PARAMS: Self::Params = ();
+
+            fn parse(input: &str) -> Result<Self::Input<'_>> {
+                Ok(input)
+            }
+
+            fn solve_part_one(_: Self::Input<'_>, _: Self::Params) -> Result<String> {
+                Err(Error::unsolvable("part one isn't solved yet"))
+            }
+
+            fn solve_part_two(_: Self::Input<'_>, _: Self::Params) -> Result<String> {
+                Err(Error::unsolvable("part two isn't solved yet"))
+            }
+        }
+
+        let src = temp_dir("scaffold");
+        for file in ["lib.rs", "registry.rs"] {
+            fs::copy(Path::new("src").join(file), src.join(file)).unwrap();
+        }
+        scaffold::new_day(&src, Unsolved::DAY).unwrap();
+
+        let inputs = InputsDir(src);
+        let runs =
+            Part::ALL.map(|part| run(&Day::of::<Unsolved>(), part, &inputs, InputKind::Example));
+        check_example_answers(&runs, &inputs);
+    }
+
+    #[test]
+    fn test_real_answers() {
+        let runs = run_all(&InputsDir::default(), InputKind::Real);
+        let table = Table(&runs);
+        assert!(table.all_passed(), "\n{table}");
+    }
+
+    #[test]
+    fn test_broken_answers_keep_the_answer() {
+        let inputs = InputsDir(temp_dir("broken-answers"));
+        let day_dir = inputs.day_dir(1);
+        fs::create_dir_all(&day_dir).unwrap();
+        fs::copy(
+            InputsDir::default().input_path(1, InputKind::Example),
+            inputs.input_path(1, InputKind::Example),
+        )
+        .unwrap();
+        fs::write(day_dir.join(Answers::FILENAME), "[example\npart_one = 1").unwrap();
+
+        let runs = [run(&DAYS[0], Part::One, &inputs, InputKind::Example)];
+        assert_eq!(runs[0].answer.as_deref().unwrap(), "24000");
+        assert_eq!(runs[0].status, Status::Unchecked);
+        assert!(runs[0].warning.is_some());
+        assert!(Table(&runs).to_string().contains("warning: day 1: "));
+    }
+}
@@ -0,0 +1,282 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{answers::Answers, error::Result};
+
+/// The source of a new day's module. `__DAY__` is replaced by the day number.
+const TEMPLATE: &str = r#"use crate::{
+    error::{Error, Result},
+    solution::Solution,
+};
+
+pub struct Day__DAY__;
+
+impl Solution for Day__DAY__ {
+    const DAY: u32 = __DAY__;
+
+    type Input<'a> = Vec<&'a str>;
+    type Params = ();
+
+    const PARAMS: Self::Params = ();
+    const EXAMPLE_PARAMS: Self::Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        parsing::input(input)
+    }
+
+    fn solve_part_one(_lines: Self::Input<'_>, _: Self::Params) -> Result<String> {
+        Err(Error::unsolvable("part one isn't solved yet"))
+    }
+
+    fn solve_part_two(_lines: Self::Input<'_>, _: Self::Params) -> Result<String> {
+        Err(Error::unsolvable("part two isn't solved yet"))
+    }
+}
+
+mod parsing {
+    use nom::{
+        character::complete::{line_ending, not_line_ending},
+        multi::separated_list1,
+    };
+
+    use crate::{common::parsing, error::Result};
+
+    pub(super) fn input(input: &str) -> Result<Vec<&str>> {
+        parsing::complete(input, separated_list1(line_ending, not_line_ending))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{InputKind, Part};
+
+    const INPUT: &str = "";
+
+    #[test]
+    #[ignore = "not solved yet"]
+    fn test_part_one() {
+        let expected = "";
+        assert_eq!(
+            Day__DAY__::solve(INPUT, Part::One, InputKind::Example).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    #[ignore = "not solved yet"]
+    fn test_part_two() {
+        let expected = "";
+        assert_eq!(
+            Day__DAY__::solve(INPUT, Part::Two, InputKind::Example).unwrap(),
+            expected
+        );
+    }
+}
+"#;
+
+const ANSWERS_TEMPLATE: &str = r#"[example]
+# part_one = ""
+# part_two = ""
+"#;
+
+/// Creates `dayN/` in the source directory `src` with a module, an empty example input and
+/// answers to fill in, and registers the module in `lib.rs` and `registry.rs`.
+///
+/// Returns the paths of the files that were created or changed.
+pub fn new_day(src: &Path, day_number: u32) -> Result<Vec<PathBuf>> {
+    let day_dir = src.join(format!("day{day_number}"));
+    if day_dir.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", day_dir.display()),
+        )
+        .into());
+    }
+
+    // Register first, so nothing is left behind if the registry can't be updated.
+    let lib_path = src.join("lib.rs");
+    let registry_path = src.join("registry.rs");
+    let lib = register_module(&fs::read_to_string(&lib_path)?, day_number)?;
+    let registry = register_day(&fs::read_to_string(&registry_path)?, day_number)?;
+
+    fs::create_dir_all(&day_dir)?;
+    let files = [
+        (day_dir.join("mod.rs"), module(day_number)),
+        (day_dir.join("test_input.txt"), String::new()),
+        (
+            day_dir.join(Answers::FILENAME),
+            ANSWERS_TEMPLATE.to_string(),
+        ),
+        (lib_path, lib),
+        (registry_path, registry),
+    ];
+    for (path, contents) in &files {
+        fs::write(path, contents)?;
+    }
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+pub fn module(day_number: u32) -> String {
+    TEMPLATE.replace("__DAY__", &day_number.to_string())
+}
+
+/// Adds `pub mod dayN;` to the sorted block of day modules in `lib.rs`.
+fn register_module(lib: &str, day_number: u32) -> Result<String> {
+    insert_line(
+        lib,
+        "lib.rs",
+        |line| is_day_line(line, "pub mod day", ";"),
+        Order::String,
+        format!("pub mod day{day_number};"),
+    )
+}
+
+/// Adds `dayN::DayN` to the imports and `Day::of::<DayN>()` to `DAYS` in `registry.rs`.
+fn register_day(registry: &str, day_number: u32) -> Result<String> {
+    let registry = insert_line(
+        registry,
+        "registry.rs",
+        |line| is_day_line(line, "    day", ","),
+        Order::String,
+        format!("    day{day_number}::Day{day_number},"),
+    )?;
+    let registry = insert_line(
+        &registry,
+        "registry.rs",
+        |line| is_day_line(line, "    Day::of::<Day", ">(),"),
+        Order::DayNumber,
+        format!("    Day::of::<Day{day_number}>(),"),
+    )?;
+
+    let count = registry
+        .lines()
+        .filter(|line| is_day_line(line, "    Day::of::<Day", ">(),"))
+        .count();
+    let (before, after) = registry
+        .split_once("pub static DAYS: [Day; ")
+        .ok_or_else(|| invalid_data("registry.rs has no DAYS array"))?;
+    let (_, after) = after
+        .split_once(']')
+        .ok_or_else(|| invalid_data("registry.rs has no DAYS array"))?;
+    Ok(format!("{before}pub static DAYS: [Day; {count}]{after}"))
+}
+
+/// Whether `line` is `prefix`, a day number, then `suffix`, like `pub mod day12;`.
+fn is_day_line(line: &str, prefix: &str, suffix: &str) -> bool {
+    line.strip_prefix(prefix)
+        .and_then(|rest| rest.strip_suffix(suffix))
+        .and_then(|rest| rest.split("::").next())
+        .is_some_and(|number| !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Order {
+    /// The way rustfmt sorts modules and imports, so `day10` comes before `day2`.
+    String,
+    DayNumber,
+}
+
+/// Adds `new_line` to the first run of consecutive lines matching `in_block`, keeping it sorted.
+fn insert_line(
+    source: &str,
+    name: &str,
+    in_block: impl Fn(&str) -> bool,
+    order: Order,
+    new_line: String,
+) -> Result<String> {
+    let lines = source.lines().collect::<Vec<_>>();
+    let start = lines
+        .iter()
+        .position(|line| in_block(line))
+        .ok_or_else(|| invalid_data(format!("{name} has no day to add `{new_line}` next to")))?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .take_while(|line| in_block(line))
+            .count();
+    let block = &lines[start..end];
+    if block.contains(&new_line.as_str()) {
+        return Err(invalid_data(format!("{name} already has `{new_line}`")).into());
+    }
+
+    // Every line in the block has a single day number, so that's all there is to sort by.
+    let key = |line: &str| -> (u32, String) {
+        let number = line
+            .split(|ch: char| !ch.is_ascii_digit())
+            .find(|digits| !digits.is_empty())
+            .unwrap_or_default();
+        match order {
+            Order::String => (0, format!("day{number}")),
+            Order::DayNumber => (number.parse().unwrap_or_default(), String::new()),
+        }
+    };
+    let insert_at = start
+        + block
+            .iter()
+            .take_while(|line| key(line) < key(&new_line))
+            .count();
+
+    let mut lines = lines
+        .iter()
+        .map(|line| line.to_string())
+        .collect::<Vec<_>>();
+    lines.insert(insert_at, new_line);
+    let mut result = lines.join("\n");
+    if source.ends_with('\n') {
+        result.push('\n');
+    }
+    Ok(result)
+}
+
+fn invalid_data(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_module() {
+        let lib = "pub mod bench;\npub mod day1;\npub mod day10;\npub mod day2;\npub mod error;\n";
+        assert_eq!(
+            register_module(lib, 11).unwrap(),
+            "pub mod bench;\npub mod day1;\npub mod day10;\npub mod day11;\npub mod day2;\npub mod error;\n"
+        );
+        assert!(register_module(lib, 2).is_err());
+    }
+
+    #[test]
+    fn test_register_day() {
+        let registry = "use crate::{
+    day1::Day1,
+    day2::Day2,
+    error::Result,
+};
+
+pub static DAYS: [Day; 2] = [
+    Day::of::<Day1>(),
+    Day::of::<Day2>(),
+];
+";
+        assert_eq!(
+            register_day(registry, 10).unwrap(),
+            "use crate::{
+    day1::Day1,
+    day10::Day10,
+    day2::Day2,
+    error::Result,
+};
+
+pub static DAYS: [Day; 3] = [
+    Day::of::<Day1>(),
+    Day::of::<Day2>(),
+    Day::of::<Day10>(),
+];
+"
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+use crate::error::Result;
+
+/// A single day of the advent calendar.
+///
+/// `parse` turns the raw puzzle input into `Input`, which is then handed by value to one of
+/// the two parts. `Params` covers values that the puzzle text states separately for the
+/// example and for the real input (e.g. the row to check on day 15).
+pub trait Solution {
+    const DAY: u32;
+
+    type Input<'a>;
+    type Params: Copy;
+
+    const PARAMS: Self::Params;
+    const EXAMPLE_PARAMS: Self::Params;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>>;
+
+    fn solve_part_one(input: Self::Input<'_>, params: Self::Params) -> Result<String>;
+
+    fn solve_part_two(input: Self::Input<'_>, params: Self::Params) -> Result<String>;
+
+    fn solve(input: &str, part: Part, kind: InputKind) -> Result<String> {
+        Self::solve_timed(input, part, kind).map(|(answer, _)| answer)
+    }
+
+    /// Like [`Solution::solve`], but also measures parsing and solving separately.
+    fn solve_timed(input: &str, part: Part, kind: InputKind) -> Result<(String, Timings)> {
+        let params = match kind {
+            InputKind::Real => Self::PARAMS,
+            InputKind::Example => Self::EXAMPLE_PARAMS,
+        };
+        let start = Instant::now();
+        let input = Self::parse(input)?;
+        let parse = start.elapsed();
+        let start = Instant::now();
+        let answer = match part {
+            Part::One => Self::solve_part_one(input, params),
+            Part::Two => Self::solve_part_two(input, params),
+        }?;
+        let solve = start.elapsed();
+        Ok((answer, Timings { parse, solve }))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Timings {
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+impl Timings {
+    pub fn total(self) -> Duration {
+        self.parse + self.solve
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn from_number(number: u32) -> Option<Self> {
+        match number {
+            1 => Some(Self::One),
+            2 => Some(Self::Two),
+            _ => None,
+        }
+    }
+
+    pub fn number(self) -> u32 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.number(), f)
+    }
+}
+
+/// Whether a day is being run against the example from the puzzle text or the real input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputKind {
+    Real,
+    Example,
+}
@@ -0,0 +1,424 @@
+use std::{
+    fmt, fs, io,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::Client,
+    common::parsing,
+    error::{Error, Result},
+    input::InputsDir,
+    solution::Part,
+};
+
+/// What the server made of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// The answer wasn't checked because the previous one was too recent.
+    Wait,
+}
+
+impl Verdict {
+    pub fn is_wrong(self) -> bool {
+        matches!(self, Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Right => write!(f, "right"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wait => write!(f, "not checked, submitted too soon"),
+        }
+    }
+}
+
+/// The parsed response to a submission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Outcome {
+    pub verdict: Verdict,
+    /// How long the server wants us to wait before the next submission.
+    pub wait: Option<Duration>,
+}
+
+impl Outcome {
+    /// Reads the outcome from the article in the server's response page.
+    pub fn from_response(page: &str) -> Result<Self> {
+        let verdict = if page.contains("That's the right answer") {
+            Verdict::Right
+        } else if page.contains("That's not the right answer") {
+            if page.contains("your answer is too high") {
+                Verdict::TooHigh
+            } else if page.contains("your answer is too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            }
+        } else if page.contains("You gave an answer too recently") {
+            Verdict::Wait
+        } else if page.contains("You don't seem to be solving the right level") {
+            return Err(Error::http(
+                "this part is already solved or not unlocked yet",
+            ));
+        } else {
+            return Err(Error::http("couldn't make sense of the response"));
+        };
+        Ok(Self {
+            verdict,
+            wait: parse_wait(page),
+        })
+    }
+}
+
+/// Understands "You have 1m 5s left to wait" and "Please wait one minute before trying again".
+fn parse_wait(page: &str) -> Option<Duration> {
+    if let Some((_, rest)) = page.split_once("You have ") {
+        let (left, _) = rest.split_once(" left to wait")?;
+        return left
+            .split_whitespace()
+            .map(|amount| {
+                let (number, unit) = amount.split_at(amount.find(|ch: char| !ch.is_ascii_digit())?);
+                let number = number.parse::<u64>().ok()?;
+                match unit {
+                    "h" => Some(number * 3600),
+                    "m" => Some(number * 60),
+                    "s" => Some(number),
+                    _ => None,
+                }
+            })
+            .sum::<Option<u64>>()
+            .map(Duration::from_secs);
+    }
+
+    let (_, rest) = page.split_once("lease wait ")?;
+    let (amount, _) = rest.split_once(" before trying again")?;
+    let minutes = match amount.split_whitespace().next()? {
+        "one" => 1,
+        number => number.parse::<u64>().ok()?,
+    };
+    Some(Duration::from_secs(minutes * 60))
+}
+
+/// One submitted answer, as stored in the history file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Attempt {
+    pub part: u32,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the Unix epoch.
+    pub submitted_at: u64,
+    /// No answer should be submitted before this, in seconds since the Unix epoch.
+    pub retry_after: Option<u64>,
+}
+
+/// Why an answer wasn't submitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved {
+        answer: String,
+    },
+    KnownWrong {
+        verdict: Verdict,
+    },
+    /// The answer is at or below an answer that was too low.
+    BelowBound {
+        too_low: i64,
+    },
+    /// The answer is at or above an answer that was too high.
+    AboveBound {
+        too_high: i64,
+    },
+    RateLimited {
+        remaining: Duration,
+    },
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::AlreadySolved { answer } => write!(f, "already solved with {answer}"),
+            Refusal::KnownWrong { verdict } => {
+                write!(f, "this answer was already submitted and was {verdict}")
+            }
+            Refusal::BelowBound { too_low } => write!(f, "{too_low} was already too low"),
+            Refusal::AboveBound { too_high } => write!(f, "{too_high} was already too high"),
+            Refusal::RateLimited { remaining } => {
+                write!(f, "wait {}s before submitting again", remaining.as_secs())
+            }
+        }
+    }
+}
+
+/// Every answer submitted for a day, stored in `submissions.toml` next to the day's input.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct History {
+    #[serde(default, rename = "attempt")]
+    pub attempts: Vec<Attempt>,
+}
+
+impl History {
+    pub const FILENAME: &'static str = "submissions.toml";
+
+    pub fn path(inputs: &InputsDir, day_number: u32) -> PathBuf {
+        inputs.day_dir(day_number).join(Self::FILENAME)
+    }
+
+    /// Loads the history of a day. A missing file means nothing was submitted yet.
+    pub fn load(inputs: &InputsDir, day_number: u32) -> Result<Self> {
+        let path = Self::path(inputs, day_number);
+        let history = match fs::read_to_string(path) {
+            Ok(history) => history,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err.into()),
+        };
+        parsing::toml(&history)
+    }
+
+    pub fn save(&self, inputs: &InputsDir, day_number: u32) -> Result<()> {
+        let history =
+            toml::to_string(self).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        fs::create_dir_all(inputs.day_dir(day_number))?;
+        fs::write(Self::path(inputs, day_number), history)?;
+        Ok(())
+    }
+
+    /// Returns why `answer` shouldn't be submitted for `part` at `now`, if there's a reason.
+    pub fn check(&self, part: Part, answer: &str, now: SystemTime) -> Option<Refusal> {
+        if let Some(right) = self
+            .attempts_for(part)
+            .find(|attempt| attempt.verdict == Verdict::Right)
+        {
+            return Some(Refusal::AlreadySolved {
+                answer: right.answer.clone(),
+            });
+        }
+
+        let retry_after = self
+            .attempts
+            .iter()
+            .filter_map(|attempt| attempt.retry_after)
+            .max();
+        if let Some(remaining) = retry_after
+            .map(|retry_after| Duration::from_secs(retry_after.saturating_sub(unix_time(now))))
+            .filter(|remaining| !remaining.is_zero())
+        {
+            return Some(Refusal::RateLimited { remaining });
+        }
+
+        if let Some(wrong) = self
+            .attempts_for(part)
+            .find(|attempt| attempt.verdict.is_wrong() && attempt.answer == answer)
+        {
+            return Some(Refusal::KnownWrong {
+                verdict: wrong.verdict,
+            });
+        }
+
+        let number = answer.trim().parse::<i64>().ok()?;
+        let (too_low, too_high) = self.bounds(part);
+        match (too_low, too_high) {
+            (Some(too_low), _) if number <= too_low => Some(Refusal::BelowBound { too_low }),
+            (_, Some(too_high)) if number >= too_high => Some(Refusal::AboveBound { too_high }),
+            _ => None,
+        }
+    }
+
+    /// The highest answer that was too low and the lowest answer that was too high.
+    pub fn bounds(&self, part: Part) -> (Option<i64>, Option<i64>) {
+        let numbers = |verdict| {
+            self.attempts_for(part)
+                .filter(move |attempt| attempt.verdict == verdict)
+                .filter_map(|attempt| attempt.answer.trim().parse::<i64>().ok())
+        };
+        (
+            numbers(Verdict::TooLow).max(),
+            numbers(Verdict::TooHigh).min(),
+        )
+    }
+
+    pub fn record(&mut self, part: Part, answer: &str, outcome: Outcome, now: SystemTime) {
+        let submitted_at = unix_time(now);
+        self.attempts.push(Attempt {
+            part: part.number(),
+            answer: answer.to_string(),
+            verdict: outcome.verdict,
+            submitted_at,
+            retry_after: outcome.wait.map(|wait| submitted_at + wait.as_secs()),
+        });
+    }
+
+    fn attempts_for(&self, part: Part) -> impl Iterator<Item = &Attempt> {
+        self.attempts
+            .iter()
+            .filter(move |attempt| attempt.part == part.number())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Submission {
+    Sent(Outcome),
+    /// The answer wasn't sent, because the history says it can't be right or it's too soon.
+    Refused(Refusal),
+}
+
+/// Submits an answer unless the day's history rules it out, and records the outcome.
+pub fn submit(
+    client: &Client,
+    inputs: &InputsDir,
+    day_number: u32,
+    part: Part,
+    answer: &str,
+) -> Result<Submission> {
+    let mut history = History::load(inputs, day_number)?;
+    let now = SystemTime::now();
+    if let Some(refusal) = history.check(part, answer, now) {
+        return Ok(Submission::Refused(refusal));
+    }
+
+    let page = client.post_answer(day_number, part, answer)?;
+    let outcome = Outcome::from_response(&page)?;
+    history.record(part, answer, outcome, now);
+    history.save(inputs, day_number)?;
+    Ok(Submission::Sent(outcome))
+}
+
+fn unix_time(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::tests::{temp_dir, MockServer};
+
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high. \
+        If you're stuck, make sure you're using the full input data. Please wait one minute \
+        before trying again. [<a href=\"/2022/day/1\">Return to Day 1</a>]</p></article>";
+
+    #[test]
+    fn test_outcome_from_response() {
+        assert_eq!(
+            Outcome::from_response(TOO_HIGH).unwrap(),
+            Outcome {
+                verdict: Verdict::TooHigh,
+                wait: Some(Duration::from_secs(60)),
+            }
+        );
+        let page = "<p>That's the right answer! You are one gold star closer.</p>";
+        assert_eq!(
+            Outcome::from_response(page).unwrap(),
+            Outcome {
+                verdict: Verdict::Right,
+                wait: None,
+            }
+        );
+        let page = "<p>You gave an answer too recently; you have to wait after submitting an \
+            answer before trying again.  You have 1m 5s left to wait.</p>";
+        assert_eq!(
+            Outcome::from_response(page).unwrap(),
+            Outcome {
+                verdict: Verdict::Wait,
+                wait: Some(Duration::from_secs(65)),
+            }
+        );
+        assert!(Outcome::from_response("<html></html>").is_err());
+    }
+
+    #[test]
+    fn test_history_check() {
+        let start = UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let wrong = |verdict| Outcome {
+            verdict,
+            wait: Some(Duration::from_secs(60)),
+        };
+        let mut history = History::default();
+        history.record(Part::One, "100", wrong(Verdict::TooLow), start);
+        history.record(Part::One, "500", wrong(Verdict::TooHigh), start);
+
+        let later = start + Duration::from_secs(120);
+        assert_eq!(
+            history.check(Part::One, "200", start + Duration::from_secs(20)),
+            Some(Refusal::RateLimited {
+                remaining: Duration::from_secs(40)
+            })
+        );
+        assert_eq!(
+            history.check(Part::One, "100", later),
+            Some(Refusal::KnownWrong {
+                verdict: Verdict::TooLow
+            })
+        );
+        assert_eq!(
+            history.check(Part::One, "50", later),
+            Some(Refusal::BelowBound { too_low: 100 })
+        );
+        assert_eq!(
+            history.check(Part::One, "501", later),
+            Some(Refusal::AboveBound { too_high: 500 })
+        );
+        assert_eq!(history.check(Part::One, "200", later), None);
+        assert_eq!(history.check(Part::Two, "50", later), None);
+
+        let right = Outcome {
+            verdict: Verdict::Right,
+            wait: None,
+        };
+        history.record(Part::One, "200", right, later);
+        assert_eq!(
+            history.check(Part::One, "300", later),
+            Some(Refusal::AlreadySolved {
+                answer: "200".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_submit() {
+        let server = MockServer::start(|_| (200, TOO_HIGH.to_string()));
+        let client = Client::new(&server.url, Some("abc".to_string()));
+        let inputs = InputsDir(temp_dir("submit"));
+
+        let submission = submit(&client, &inputs, 1, Part::Two, "1234").unwrap();
+        assert!(
+            matches!(
+                submission,
+                Submission::Sent(Outcome {
+                    verdict: Verdict::TooHigh,
+                    ..
+                })
+            ),
+            "{submission:?}"
+        );
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2022/day/1/answer");
+        assert_eq!(requests[0].body, "level=2&answer=1234");
+
+        let history = History::load(&inputs, 1).unwrap();
+        assert_eq!(history.attempts.len(), 1);
+        assert_eq!(history.bounds(Part::Two), (None, Some(1234)));
+
+        let submission = submit(&client, &inputs, 1, Part::Two, "1234").unwrap();
+        assert!(
+            matches!(submission, Submission::Refused(_)),
+            "{submission:?}"
+        );
+        assert_eq!(server.requests().len(), 1);
+    }
+}